@@ -124,6 +124,7 @@ impl PreviewApp {
                     g: 255,
                     b: 255,
                 },
+                gamma: None,
             },
             timings: PREVIEW_TRANSITION_TIMINGS,
             brightness: initial_brightness,
@@ -392,6 +393,71 @@ impl eframe::App for PreviewApp {
                                 EffectId::Sunset,
                                 EffectId::Sunset.as_str(),
                             );
+                            ui.selectable_value(
+                                &mut selected_effect,
+                                EffectId::Circadian,
+                                EffectId::Circadian.as_str(),
+                            );
+                            ui.selectable_value(
+                                &mut selected_effect,
+                                EffectId::Plasma,
+                                EffectId::Plasma.as_str(),
+                            );
+                            ui.selectable_value(
+                                &mut selected_effect,
+                                EffectId::Heartbeat,
+                                EffectId::Heartbeat.as_str(),
+                            );
+                            ui.selectable_value(
+                                &mut selected_effect,
+                                EffectId::HeartbeatRipple,
+                                EffectId::HeartbeatRipple.as_str(),
+                            );
+                            ui.selectable_value(
+                                &mut selected_effect,
+                                EffectId::StrobeHalves,
+                                EffectId::StrobeHalves.as_str(),
+                            );
+                            ui.selectable_value(
+                                &mut selected_effect,
+                                EffectId::StrobeAlternate,
+                                EffectId::StrobeAlternate.as_str(),
+                            );
+                            ui.selectable_value(
+                                &mut selected_effect,
+                                EffectId::StrobeQuad,
+                                EffectId::StrobeQuad.as_str(),
+                            );
+                            ui.selectable_value(
+                                &mut selected_effect,
+                                EffectId::Gauge,
+                                EffectId::Gauge.as_str(),
+                            );
+                            ui.selectable_value(
+                                &mut selected_effect,
+                                EffectId::Clock,
+                                EffectId::Clock.as_str(),
+                            );
+                            ui.selectable_value(
+                                &mut selected_effect,
+                                EffectId::Rain,
+                                EffectId::Rain.as_str(),
+                            );
+                            ui.selectable_value(
+                                &mut selected_effect,
+                                EffectId::Gradient,
+                                EffectId::Gradient.as_str(),
+                            );
+                            ui.selectable_value(
+                                &mut selected_effect,
+                                EffectId::VuMeter,
+                                EffectId::VuMeter.as_str(),
+                            );
+                            ui.selectable_value(
+                                &mut selected_effect,
+                                EffectId::Spectrum,
+                                EffectId::Spectrum.as_str(),
+                            );
                         });
                     if selected_effect != self.effect_id {
                         self.effect_id = selected_effect;
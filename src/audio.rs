@@ -0,0 +1,237 @@
+//! Audio level input
+//!
+//! A microphone task publishes levels and frequency spectra into an
+//! [`AudioFeed`], and the renderer reads the latest ones every frame. Only the
+//! most recent values are kept, so the producer never blocks and a slow
+//! renderer never lags behind.
+
+use core::cell::{Cell, RefCell};
+
+use critical_section::Mutex;
+use embassy_time::{Duration, Instant};
+use heapless::Vec;
+
+use crate::math8::scale8;
+
+/// Time without new levels after which the input is considered stale
+pub const AUDIO_STALE_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Time for stale input to decay to silence
+const STALE_DECAY_MS: u64 = 500;
+
+/// Minimum number of spectrum bins
+pub const MIN_SPECTRUM_BINS: usize = 8;
+/// Maximum number of spectrum bins
+pub const MAX_SPECTRUM_BINS: usize = 32;
+
+/// Audio level measured by the microphone task
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AudioLevel {
+    /// Peak amplitude (0-255)
+    pub peak: u8,
+    /// RMS amplitude (0-255)
+    pub rms: u8,
+}
+
+impl AudioLevel {
+    /// No sound
+    pub const SILENCE: Self = Self { peak: 0, rms: 0 };
+
+    /// Create a new audio level
+    pub const fn new(peak: u8, rms: u8) -> Self {
+        Self { peak, rms }
+    }
+
+    /// Scale both amplitudes by `scale` (0-255)
+    #[must_use]
+    pub const fn scaled(self, scale: u8) -> Self {
+        Self {
+            peak: scale8(self.peak, scale),
+            rms: scale8(self.rms, scale),
+        }
+    }
+}
+
+/// Frequency bin magnitudes computed by an external FFT
+///
+/// Bins are ordered from low to high frequencies. Magnitudes are unscaled,
+/// the spectrum effect applies its own auto-gain.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Spectrum {
+    bins: Vec<u16, MAX_SPECTRUM_BINS>,
+}
+
+impl Spectrum {
+    /// Create a spectrum from bin magnitudes
+    ///
+    /// Returns `None` unless there are 8 to 32 bins.
+    pub fn new(bins: &[u16]) -> Option<Self> {
+        if bins.len() < MIN_SPECTRUM_BINS {
+            return None;
+        }
+        Some(Self {
+            bins: Vec::from_slice(bins).ok()?,
+        })
+    }
+
+    /// Create a silent spectrum with the given number of bins
+    fn silent(len: usize) -> Self {
+        Self {
+            bins: (0..len.min(MAX_SPECTRUM_BINS)).map(|_| 0).collect(),
+        }
+    }
+
+    /// Get the bin magnitudes
+    pub fn bins(&self) -> &[u16] {
+        &self.bins
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct SpectrumSample {
+    spectrum: Spectrum,
+    sequence: u32,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct AudioSample {
+    level: AudioLevel,
+    /// Incremented on every update, so repeated levels are still fresh
+    sequence: u32,
+}
+
+/// Latest audio level shared between the microphone task and the renderer
+///
+/// Access is guarded by critical sections, like [`crate::channel::Channel`].
+pub struct AudioFeed {
+    sample: Mutex<Cell<AudioSample>>,
+    spectrum: Mutex<RefCell<SpectrumSample>>,
+}
+
+impl AudioFeed {
+    /// Create a new silent feed
+    pub const fn new() -> Self {
+        Self {
+            sample: Mutex::new(Cell::new(AudioSample {
+                level: AudioLevel::SILENCE,
+                sequence: 0,
+            })),
+            spectrum: Mutex::new(RefCell::new(SpectrumSample {
+                spectrum: Spectrum { bins: Vec::new() },
+                sequence: 0,
+            })),
+        }
+    }
+
+    /// Publish a new audio level
+    pub fn update(&self, level: AudioLevel) {
+        critical_section::with(|cs| {
+            let cell = self.sample.borrow(cs);
+            let sequence = cell.get().sequence.wrapping_add(1);
+            cell.set(AudioSample { level, sequence });
+        });
+    }
+
+    /// Publish a new frequency spectrum
+    pub fn update_spectrum(&self, spectrum: &Spectrum) {
+        critical_section::with(|cs| {
+            let mut sample = self.spectrum.borrow(cs).borrow_mut();
+            sample.spectrum.clone_from(spectrum);
+            sample.sequence = sample.sequence.wrapping_add(1);
+        });
+    }
+
+    fn latest(&self) -> AudioSample {
+        critical_section::with(|cs| self.sample.borrow(cs).get())
+    }
+
+    /// Get the latest spectrum if its sequence differs from `sequence`
+    fn spectrum_since(&self, sequence: u32) -> Option<(Spectrum, u32)> {
+        critical_section::with(|cs| {
+            let sample = self.spectrum.borrow(cs).borrow();
+            (sample.sequence != sequence)
+                .then(|| (sample.spectrum.clone(), sample.sequence))
+        })
+    }
+}
+
+impl Default for AudioFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reader side of an [`AudioFeed`] that tracks staleness
+#[derive(Clone, Copy)]
+pub struct AudioInput<'a> {
+    feed: &'a AudioFeed,
+    sequence: u32,
+    level: AudioLevel,
+    updated_at: Option<Instant>,
+    spectrum_sequence: u32,
+    /// Bin count and arrival time of the last spectrum, until it goes stale
+    spectrum_updated: Option<(usize, Instant)>,
+}
+
+impl<'a> AudioInput<'a> {
+    /// Create a new reader for a feed
+    pub const fn new(feed: &'a AudioFeed) -> Self {
+        Self {
+            feed,
+            sequence: 0,
+            level: AudioLevel::SILENCE,
+            updated_at: None,
+            spectrum_sequence: 0,
+            spectrum_updated: None,
+        }
+    }
+
+    /// Get the spectrum published since the last call
+    ///
+    /// Returns `None` when there is nothing new. Once no spectrum arrived
+    /// for [`AUDIO_STALE_TIMEOUT`], a silent spectrum is returned once.
+    pub fn spectrum(&mut self, now: Instant) -> Option<Spectrum> {
+        if let Some((spectrum, sequence)) =
+            self.feed.spectrum_since(self.spectrum_sequence)
+        {
+            self.spectrum_sequence = sequence;
+            self.spectrum_updated = Some((spectrum.bins().len(), now));
+            return Some(spectrum);
+        }
+
+        let (len, updated_at) = self.spectrum_updated?;
+        if now.saturating_duration_since(updated_at) <= AUDIO_STALE_TIMEOUT {
+            return None;
+        }
+        self.spectrum_updated = None;
+        Some(Spectrum::silent(len))
+    }
+
+    /// Get the current level
+    ///
+    /// Once no new level arrived for [`AUDIO_STALE_TIMEOUT`], the last level
+    /// fades out to silence.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn level(&mut self, now: Instant) -> AudioLevel {
+        let sample = self.feed.latest();
+        if sample.sequence != self.sequence {
+            self.sequence = sample.sequence;
+            self.level = sample.level;
+            self.updated_at = Some(now);
+        }
+
+        let Some(updated_at) = self.updated_at else {
+            return AudioLevel::SILENCE;
+        };
+        let age = now.saturating_duration_since(updated_at);
+        if age <= AUDIO_STALE_TIMEOUT {
+            return self.level;
+        }
+        let stale_ms = (age - AUDIO_STALE_TIMEOUT).as_millis();
+        if stale_ms >= STALE_DECAY_MS {
+            return AudioLevel::SILENCE;
+        }
+        let remaining = 255 - (stale_ms * 255 / STALE_DECAY_MS) as u8;
+        self.level.scaled(remaining)
+    }
+}
@@ -0,0 +1,228 @@
+//! Beat detection and tracking
+//!
+//! [`BeatDetector`] finds onsets in a stream of audio energy values and
+//! estimates the tempo. [`BeatTracker`] turns detected beats, or a fixed tempo
+//! when there is no audio, into a [`Beat`] that effects can follow.
+
+use embassy_time::{Duration, Instant};
+
+use crate::{effect::DEFAULT_BPM, math8::scale8};
+
+/// Shortest interval between two detected beats (200 BPM)
+const MIN_BEAT_INTERVAL_MS: u64 = 300;
+/// Longest interval counted towards the tempo estimate (30 BPM)
+const MAX_BEAT_INTERVAL_MS: u64 = 2_000;
+/// Energy above the running average that counts as an onset (1.5x)
+const ONSET_THRESHOLD: u32 = 384;
+/// Energy below which no onsets are detected
+const MIN_ONSET_ENERGY: u8 = 24;
+/// Running average weight of a new energy value (out of 256)
+const AVERAGE_WEIGHT: u32 = 16;
+/// Number of beat intervals averaged for the tempo estimate
+const TEMPO_INTERVALS: usize = 8;
+/// Minimum number of intervals before a tempo is reported
+const MIN_TEMPO_INTERVALS: usize = 3;
+/// Time without detected beats after which the fixed tempo takes over
+const AUDIO_BEAT_TIMEOUT: Duration = Duration::from_millis(3_000);
+/// Length of the beat pulse envelope
+const PULSE_MS: u64 = 180;
+
+/// Onset detector with tempo estimate
+#[derive(Debug, Clone)]
+pub struct BeatDetector {
+    /// Running average energy in 8.8 fixed-point
+    average: u32,
+    previous: u8,
+    last_beat: Option<Instant>,
+    intervals: [u16; TEMPO_INTERVALS],
+    interval_count: usize,
+    next_interval: usize,
+}
+
+impl Default for BeatDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BeatDetector {
+    /// Create a new detector
+    pub const fn new() -> Self {
+        Self {
+            average: 0,
+            previous: 0,
+            last_beat: None,
+            intervals: [0; TEMPO_INTERVALS],
+            interval_count: 0,
+            next_interval: 0,
+        }
+    }
+
+    /// Feed an energy value (e.g. RMS level), returns true on a beat
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn update(&mut self, energy: u8, now: Instant) -> bool {
+        let scaled = u32::from(energy) << 8;
+        let rising = energy > self.previous;
+        let loud = scaled * 256 > self.average * ONSET_THRESHOLD;
+        self.previous = energy;
+        self.average =
+            (self.average * (256 - AVERAGE_WEIGHT) + scaled * AVERAGE_WEIGHT) / 256;
+
+        let since_last = self
+            .last_beat
+            .map(|last| now.saturating_duration_since(last).as_millis());
+        let ready = since_last.is_none_or(|ms| ms >= MIN_BEAT_INTERVAL_MS);
+        if !(rising && loud && ready && energy >= MIN_ONSET_ENERGY) {
+            return false;
+        }
+
+        if let Some(ms) = since_last.filter(|ms| *ms <= MAX_BEAT_INTERVAL_MS) {
+            self.intervals[self.next_interval] = ms as u16;
+            self.next_interval = (self.next_interval + 1) % TEMPO_INTERVALS;
+            self.interval_count = (self.interval_count + 1).min(TEMPO_INTERVALS);
+        }
+        self.last_beat = Some(now);
+        true
+    }
+
+    /// Get the estimated tempo in beats per minute
+    ///
+    /// Returns `None` until a few regular beats were detected.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn bpm(&self) -> Option<u16> {
+        if self.interval_count < MIN_TEMPO_INTERVALS {
+            return None;
+        }
+        let total: u32 = self.intervals[..self.interval_count]
+            .iter()
+            .map(|interval| u32::from(*interval))
+            .sum();
+        let average = total / self.interval_count as u32;
+        Some((60_000 / average.max(1)) as u16)
+    }
+
+    /// Get the time of the last detected beat
+    pub const fn last_beat(&self) -> Option<Instant> {
+        self.last_beat
+    }
+}
+
+/// Current beat state shared with effects
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Beat {
+    /// Envelope peaking at 255 on every beat and decaying quickly
+    pub pulse: u8,
+    /// Number of beats so far, increments on every beat
+    pub count: u32,
+    /// Current tempo in beats per minute
+    pub bpm: u16,
+}
+
+/// How the light follows the beat
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BeatSync {
+    /// Let effects react to beats, e.g. rainbow hue jumps or flow surges
+    pub effects: bool,
+    /// Brightness dip between beats (0 = off, 255 = dark between beats)
+    pub brightness_kick: u8,
+}
+
+impl BeatSync {
+    /// Check if anything follows the beat
+    pub const fn is_enabled(&self) -> bool {
+        self.effects || self.brightness_kick > 0
+    }
+
+    /// Brightness scale for the current beat
+    pub const fn brightness(&self, beat: &Beat) -> u8 {
+        255 - scale8(255 - beat.pulse, self.brightness_kick)
+    }
+}
+
+/// Beat source following audio onsets, or a fixed tempo without audio
+#[derive(Debug, Clone)]
+pub struct BeatTracker {
+    detector: BeatDetector,
+    fallback_bpm: u16,
+    count: u32,
+    last_beat: Option<Instant>,
+}
+
+impl Default for BeatTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BeatTracker {
+    /// Create a new tracker at the default tempo
+    pub const fn new() -> Self {
+        Self {
+            detector: BeatDetector::new(),
+            fallback_bpm: DEFAULT_BPM,
+            count: 0,
+            last_beat: None,
+        }
+    }
+
+    /// Set the tempo used while no beats are detected in the audio
+    pub fn set_fallback_bpm(&mut self, bpm: u16) {
+        self.fallback_bpm = bpm.max(1);
+    }
+
+    /// Advance the tracker
+    ///
+    /// `energy` is the current audio energy, if there is an audio input.
+    pub fn update(&mut self, energy: Option<u8>, now: Instant) -> Beat {
+        if energy.is_some_and(|energy| self.detector.update(energy, now)) {
+            self.beat(now);
+        }
+
+        let audio_active = self.detector.last_beat().is_some_and(|last| {
+            now.saturating_duration_since(last) < AUDIO_BEAT_TIMEOUT
+        });
+        let bpm = if audio_active {
+            self.detector.bpm().unwrap_or(self.fallback_bpm)
+        } else {
+            let period =
+                Duration::from_millis(60_000 / u64::from(self.fallback_bpm));
+            match self.last_beat {
+                // Keep the grid, unless far behind after a pause
+                Some(last) if now.saturating_duration_since(last) >= period * 2 => {
+                    self.beat(now);
+                }
+                Some(last) if now.saturating_duration_since(last) >= period => {
+                    self.beat(last + period);
+                }
+                None => self.beat(now),
+                Some(_) => {}
+            }
+            self.fallback_bpm
+        };
+
+        Beat {
+            pulse: self.pulse(now),
+            count: self.count,
+            bpm,
+        }
+    }
+
+    fn beat(&mut self, at: Instant) {
+        self.count = self.count.wrapping_add(1);
+        self.last_beat = Some(at);
+    }
+
+    /// Envelope of the last beat
+    #[allow(clippy::cast_possible_truncation)]
+    fn pulse(&self, now: Instant) -> u8 {
+        let Some(last) = self.last_beat else {
+            return 0;
+        };
+        let elapsed = now.saturating_duration_since(last).as_millis();
+        if elapsed >= PULSE_MS {
+            return 0;
+        }
+        let remaining = 255 - (elapsed * 255 / PULSE_MS) as u8;
+        scale8(remaining, remaining)
+    }
+}
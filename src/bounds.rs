@@ -1,5 +1,3 @@
-use crate::Rgb;
-
 /// Bounds of the rendering area
 #[derive(Debug, Clone, Copy)]
 pub struct RenderingBounds {
@@ -29,7 +27,7 @@ impl RenderingBounds {
 }
 
 /// Get a slice of the LEDs within the bounds
-pub(crate) fn bounded(leds: &mut [Rgb], bounds: RenderingBounds) -> &mut [Rgb] {
+pub(crate) fn bounded<T>(leds: &mut [T], bounds: RenderingBounds) -> &mut [T] {
     let start = bounds.start;
     let end = bounds.end;
     &mut leds[start as usize..end as usize]
@@ -48,3 +46,48 @@ pub const fn center_of<T>(arr: &[T]) -> usize {
     }
     count
 }
+
+/// Full turn of a ring in [`ring_position`] angle units
+pub const RING_TURN: u32 = 1 << 16;
+
+/// Convert a ring angle to an LED position in 8.8 fixed-point
+///
+/// `angle` goes clockwise from 12 o'clock, where a full turn is
+/// [`RING_TURN`]. `top_offset` is the index of the LED at 12 o'clock.
+/// If `reversed` is set, LED indices run counter-clockwise.
+#[allow(clippy::cast_possible_truncation)]
+pub const fn ring_position(
+    len: usize,
+    top_offset: usize,
+    angle: u32,
+    reversed: bool,
+) -> u32 {
+    if len == 0 {
+        return 0;
+    }
+    let ring = (len as u32) << 8;
+    let angle = angle % RING_TURN;
+    let offset = ((top_offset % len) as u32) << 8;
+    let along = ((angle as u64 * ring as u64) >> 16) as u32;
+    if reversed {
+        (offset + ring - along) % ring
+    } else {
+        (offset + along) % ring
+    }
+}
+
+/// Distance between two 8.8 fixed-point positions on a ring of `len` LEDs
+#[allow(clippy::cast_possible_truncation)]
+pub const fn ring_distance(len: usize, a: u32, b: u32) -> u32 {
+    let ring = (len as u32) << 8;
+    if ring == 0 {
+        return 0;
+    }
+    let forward = (b % ring + ring - a % ring) % ring;
+    let backward = ring - forward;
+    if forward < backward {
+        forward
+    } else {
+        backward
+    }
+}
@@ -0,0 +1,118 @@
+//! Warm and cool white mixing for RGB+CCT strips
+//!
+//! Strips with separate warm and cool white LEDs show color temperatures by
+//! mixing the two whites, while colors are shown on the RGB LEDs.
+
+use smart_leds::{CctWhite, RGBCCT};
+
+use crate::{color::Rgb, math8::scale8};
+
+/// RGB pixel with cool and warm white channels
+pub type Rgbcct = RGBCCT<u8>;
+
+/// Create an RGB+CCT pixel from a color and the white levels
+pub const fn rgbcct(color: Rgb, warm: u8, cold: u8) -> Rgbcct {
+    Rgbcct {
+        r: color.r,
+        g: color.g,
+        b: color.b,
+        a: CctWhite { cold, warm },
+    }
+}
+
+/// How color temperatures are shown
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TemperatureMix {
+    /// Mix the two whites only
+    #[default]
+    Whites,
+    /// Mix the two whites and add the RGB approximation for extra brightness
+    WhitesAndRgb,
+}
+
+/// How colors are shown
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorMix {
+    /// Use the RGB channels only
+    #[default]
+    Rgb,
+    /// Move the common part of the channels to a neutral white mix
+    ExtractWhite,
+}
+
+/// Blend policy of an RGB+CCT output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CctBlend {
+    /// How color temperatures are shown
+    pub temperature: TemperatureMix,
+    /// How colors are shown
+    pub color: ColorMix,
+}
+
+/// White channels of an RGB+CCT strip
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CctConfig {
+    /// Color temperature of the warm white LEDs
+    pub warm_kelvin: u16,
+    /// Color temperature of the cool white LEDs
+    pub cool_kelvin: u16,
+    /// Blend policy
+    pub blend: CctBlend,
+}
+
+impl Default for CctConfig {
+    fn default() -> Self {
+        Self {
+            warm_kelvin: 2_700,
+            cool_kelvin: 6_500,
+            blend: CctBlend::default(),
+        }
+    }
+}
+
+impl CctConfig {
+    /// Split a white `level` into warm and cool levels for a temperature
+    ///
+    /// Temperatures outside the range of the LEDs use one white alone.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn mix_whites(&self, kelvin: u16, level: u8) -> (u8, u8) {
+        let range = u32::from(self.cool_kelvin.saturating_sub(self.warm_kelvin));
+        if range == 0 {
+            return (level, 0);
+        }
+        let offset = u32::from(kelvin.clamp(self.warm_kelvin, self.cool_kelvin))
+            - u32::from(self.warm_kelvin);
+        let cool = scale8(level, (offset * 255 / range) as u8);
+        (level - cool, cool)
+    }
+
+    /// Convert a rendered color to RGB+CCT
+    ///
+    /// `kelvin` is the color temperature of the light, `None` while showing
+    /// colors. The white level follows the brightest channel of the color.
+    pub fn to_rgbcct(&self, color: Rgb, kelvin: Option<u16>) -> Rgbcct {
+        match (kelvin, self.blend.color) {
+            (Some(kelvin), _) => {
+                let level = color.r.max(color.g).max(color.b);
+                let (warm, cool) = self.mix_whites(kelvin, level);
+                let color = match self.blend.temperature {
+                    TemperatureMix::Whites => Rgb { r: 0, g: 0, b: 0 },
+                    TemperatureMix::WhitesAndRgb => color,
+                };
+                rgbcct(color, warm, cool)
+            }
+            (None, ColorMix::Rgb) => rgbcct(color, 0, 0),
+            (None, ColorMix::ExtractWhite) => {
+                let white = color.r.min(color.g).min(color.b);
+                let neutral = self.warm_kelvin / 2 + self.cool_kelvin / 2;
+                let (warm, cool) = self.mix_whites(neutral, white);
+                let color = Rgb {
+                    r: color.r - white,
+                    g: color.g - white,
+                    b: color.b - white,
+                };
+                rgbcct(color, warm, cool)
+            }
+        }
+    }
+}
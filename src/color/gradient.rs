@@ -1,9 +1,12 @@
 use smart_leds::hsv::hsv2rgb;
 
-use crate::color::{Hsv, Rgb};
+use crate::{
+    color::{Hsv, Rgb, blend_colors},
+    math8::{blend8, scale8},
+};
 
 /// Hue direction for gradient calculation
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GradientDirection {
     Forward,
     Backward,
@@ -102,6 +105,45 @@ pub fn fill_gradient_fp(
     }
 }
 
+/// Blend two HSV colors, going around the hue wheel in `direction`
+///
+/// Like [`fill_gradient_fp`], black and gray ends take the hue of the other
+/// end so the hue does not sweep while fading from or to them.
+pub fn blend_hsv(
+    a: Hsv,
+    b: Hsv,
+    amount_of_b: u8,
+    direction: GradientDirection,
+) -> Hsv {
+    let mut from = a;
+    let mut to = b;
+    if to.val == 0 || to.sat == 0 {
+        to.hue = from.hue;
+    }
+    if from.val == 0 || from.sat == 0 {
+        from.hue = to.hue;
+    }
+
+    let delta = to.hue.wrapping_sub(from.hue);
+    let forward = match direction {
+        GradientDirection::Forward => true,
+        GradientDirection::Backward => false,
+        GradientDirection::Shortest => delta <= 127,
+    };
+    let hue = if forward {
+        from.hue.wrapping_add(scale8(delta, amount_of_b))
+    } else {
+        from.hue
+            .wrapping_sub(scale8(delta.wrapping_neg(), amount_of_b))
+    };
+
+    Hsv {
+        hue,
+        sat: blend8(from.sat, to.sat, amount_of_b),
+        val: blend8(from.val, to.val, amount_of_b),
+    }
+}
+
 /// Fill three-color gradient using fixed-point math
 pub fn fill_gradient_three_fp(leds: &mut [Rgb], c1: Hsv, c2: Hsv, c3: Hsv) {
     if leds.is_empty() {
@@ -117,3 +159,60 @@ pub fn fill_gradient_three_fp(leds: &mut [Rgb], c1: Hsv, c2: Hsv, c3: Hsv) {
         fill_gradient_fp(leds, half, c2, last, c3, GradientDirection::Forward);
     }
 }
+
+/// A color at a position (0-255) along a gradient
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColorStop {
+    /// Position along the gradient (0 = start, 255 = end)
+    pub position: u8,
+    /// Color at this position
+    pub color: Rgb,
+}
+
+impl ColorStop {
+    /// Create a new color stop
+    pub const fn new(position: u8, color: Rgb) -> Self {
+        Self { position, color }
+    }
+}
+
+/// Sample a list of color stops at position `t` (0-255)
+///
+/// Stops must be sorted by position. Colors between stops are blended. If
+/// `cyclic` is set, the gradient wraps from the last stop back to the first
+/// one; otherwise the end colors extend to the edges.
+#[allow(clippy::cast_possible_truncation)]
+pub fn sample_stops(stops: &[ColorStop], t: u8, cyclic: bool) -> Rgb {
+    let (Some(first), Some(last)) = (stops.first(), stops.last()) else {
+        return Rgb { r: 0, g: 0, b: 0 };
+    };
+
+    let blend_between = |from: &ColorStop, to: &ColorStop, t: u16, span: u16| {
+        let offset = (t + 256 - u16::from(from.position)) % 256;
+        let amount = (offset * 255)
+            .checked_div(span)
+            .map_or(255, |amount| amount.min(255) as u8);
+        blend_colors(from.color, to.color, amount)
+    };
+
+    if t < first.position || t >= last.position {
+        if !cyclic || stops.len() == 1 {
+            return if t < first.position {
+                first.color
+            } else {
+                last.color
+            };
+        }
+        let span = u16::from(first.position) + 256 - u16::from(last.position);
+        return blend_between(last, first, u16::from(t), span);
+    }
+
+    let next = stops
+        .iter()
+        .position(|stop| stop.position > t)
+        .unwrap_or(stops.len() - 1);
+    let from = &stops[next.saturating_sub(1)];
+    let to = &stops[next];
+    let span = u16::from(to.position) - u16::from(from.position);
+    blend_between(from, to, u16::from(t), span)
+}
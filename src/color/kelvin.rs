@@ -1,4 +1,34 @@
+//! Color temperature to RGB conversion
+//!
+//! Two implementations of the same curve: [`kelvin_to_rgb_float`] uses f32
+//! math, [`kelvin_to_rgb_fixed`] interpolates tables generated at compile
+//! time with integer math only, for microcontrollers without an FPU.
+//! [`kelvin_to_rgb`] uses the float one, or the fixed-point one with the
+//! `integer-kelvin` feature.
+
 use super::Rgb;
+use crate::math8::{const_ln, const_powf};
+
+/// Lowest supported temperature in Kelvin
+const MIN_KELVIN: u16 = 1_000;
+/// Highest supported temperature in Kelvin
+const MAX_KELVIN: u16 = 40_000;
+/// Temperature where the curves switch to their cool parts
+const NEUTRAL_KELVIN: u16 = 6_600;
+/// Temperature where the cool table switches to coarser steps
+const COARSE_KELVIN: u16 = 10_000;
+/// Step of the warm table and the fine part of the cool table
+const FINE_STEP: u16 = 100;
+/// Step of the coarse part of the cool table
+const COARSE_STEP: u16 = 1_000;
+
+/// Size of the warm table, 1000K to 6600K
+const WARM_LEN: usize = ((NEUTRAL_KELVIN - MIN_KELVIN) / FINE_STEP) as usize + 1;
+/// Number of fine steps in the cool table, 6600K to 10000K
+const COOL_FINE_LEN: usize = ((COARSE_KELVIN - NEUTRAL_KELVIN) / FINE_STEP) as usize;
+/// Size of the cool table, 6600K to 40000K
+const COOL_LEN: usize =
+    COOL_FINE_LEN + ((MAX_KELVIN - COARSE_KELVIN) / COARSE_STEP) as usize + 1;
 
 #[allow(clippy::approx_constant)]
 const LN_LUT: [f32; 57] = [
@@ -13,16 +43,57 @@ const LN_LUT: [f32; 57] = [
     4.189_654,
 ];
 
-#[inline]
 /// Convert a Kelvin temperature to an RGB color
 ///
+/// Supports temperatures between 1000K and 40000K. Uses
+/// [`kelvin_to_rgb_fixed`] with the `integer-kelvin` feature and
+/// [`kelvin_to_rgb_float`] otherwise.
+#[inline]
+pub fn kelvin_to_rgb(kelvin: u16) -> Rgb {
+    #[cfg(feature = "integer-kelvin")]
+    {
+        kelvin_to_rgb_fixed(kelvin)
+    }
+    #[cfg(not(feature = "integer-kelvin"))]
+    {
+        kelvin_to_rgb_float(kelvin)
+    }
+}
+
+/// Convert a mired (micro reciprocal degree) temperature to an RGB color
+#[inline]
+pub fn mired_to_rgb(mired: u16) -> Rgb {
+    kelvin_to_rgb(mired_to_kelvin(mired))
+}
+
+/// Convert a mired temperature to Kelvin
+#[allow(clippy::cast_possible_truncation)]
+pub const fn mired_to_kelvin(mired: u16) -> u16 {
+    let mired = if mired == 0 { 1 } else { mired };
+    let kelvin = 1_000_000 / mired as u32;
+    if kelvin > u16::MAX as u32 {
+        u16::MAX
+    } else {
+        kelvin as u16
+    }
+}
+
+/// Convert a Kelvin temperature to mired
+#[allow(clippy::cast_possible_truncation)]
+pub const fn kelvin_to_mired(kelvin: u16) -> u16 {
+    let kelvin = if kelvin < 16 { 16 } else { kelvin };
+    (1_000_000 / kelvin as u32) as u16
+}
+
+/// Convert a Kelvin temperature to an RGB color with f32 math
+///
 /// Supports temperatures between 1000K and 40000K.
 #[allow(
     clippy::cast_lossless,
     clippy::cast_possible_truncation,
     clippy::cast_sign_loss
 )]
-pub fn kelvin_to_rgb(kelvin: u16) -> Rgb {
+pub fn kelvin_to_rgb_float(kelvin: u16) -> Rgb {
     let mut temp = (kelvin as f32 / 100.0).clamp(10.0, 400.0);
     let original_temp = temp;
 
@@ -68,3 +139,140 @@ pub fn kelvin_to_rgb(kelvin: u16) -> Rgb {
         b: blue as u8,
     }
 }
+
+/// Convert a Kelvin temperature to an RGB color with integer math
+///
+/// Supports temperatures between 1000K and 40000K. Follows
+/// [`kelvin_to_rgb_float`] within one step per channel.
+#[allow(clippy::cast_possible_truncation)]
+pub const fn kelvin_to_rgb_fixed(kelvin: u16) -> Rgb {
+    let kelvin = if kelvin < MIN_KELVIN {
+        MIN_KELVIN
+    } else if kelvin > MAX_KELVIN {
+        MAX_KELVIN
+    } else {
+        kelvin
+    };
+
+    if kelvin <= NEUTRAL_KELVIN {
+        let offset = kelvin - MIN_KELVIN;
+        let index = (offset / FINE_STEP) as usize;
+        let (green, blue) = WARM_TABLE[index];
+
+        // Green follows a continuous curve from 5700K on, steps below
+        let green = if index + 1 < WARM_LEN && index + 10 >= LN_LUT.len() {
+            let next = WARM_TABLE[index + 1].0;
+            lerp_fixed(green, next, offset % FINE_STEP, FINE_STEP)
+        } else {
+            green
+        };
+        let blue = if kelvin == NEUTRAL_KELVIN {
+            255
+        } else if kelvin <= 1_900 {
+            0
+        } else {
+            (blue >> 8) as u8
+        };
+        return Rgb {
+            r: 255,
+            g: (green >> 8) as u8,
+            b: blue,
+        };
+    }
+
+    let (index, from, step) = if kelvin < COARSE_KELVIN {
+        let offset = kelvin - NEUTRAL_KELVIN;
+        ((offset / FINE_STEP) as usize, offset % FINE_STEP, FINE_STEP)
+    } else {
+        let offset = kelvin - COARSE_KELVIN;
+        (
+            COOL_FINE_LEN + (offset / COARSE_STEP) as usize,
+            offset % COARSE_STEP,
+            COARSE_STEP,
+        )
+    };
+    let (red, green) = COOL_TABLE[index];
+    let (red, green) = if index + 1 < COOL_LEN {
+        let (next_red, next_green) = COOL_TABLE[index + 1];
+        (
+            lerp_fixed(red, next_red, from, step),
+            lerp_fixed(green, next_green, from, step),
+        )
+    } else {
+        (red, green)
+    };
+    Rgb {
+        r: (red >> 8) as u8,
+        g: (green >> 8) as u8,
+        b: 255,
+    }
+}
+
+/// Interpolate between two 8.8 fixed-point values, `at` out of `step`
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+const fn lerp_fixed(from: u16, to: u16, at: u16, step: u16) -> u16 {
+    let delta = to as i32 - from as i32;
+    (from as i32 + delta * at as i32 / step as i32) as u16
+}
+
+/// Clamp a channel value to 0-255 and convert it to 8.8 fixed point
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+const fn to_fixed(value: f64) -> u16 {
+    let value = if value < 0.0 {
+        0.0
+    } else if value > 255.0 {
+        255.0
+    } else {
+        value
+    };
+    (value * 256.0) as u16
+}
+
+/// Green and blue in 8.8 fixed point, every 100K from 1000K to 6600K
+///
+/// Red is 255 over the whole range.
+#[allow(clippy::cast_precision_loss)]
+const WARM_TABLE: [(u16, u16); WARM_LEN] = {
+    let mut table = [(0, 0); WARM_LEN];
+    let mut i = 0;
+    while i < WARM_LEN {
+        let temp = i + 10;
+        let ln = if temp < LN_LUT.len() {
+            LN_LUT[temp] as f64
+        } else {
+            const_ln(temp as f64)
+        };
+        let green = 99.470_8 * ln - 161.119_57;
+        let blue = if temp < 19 {
+            0.0
+        } else {
+            138.517_73 * LN_LUT[temp - 10] as f64 - 305.044_8
+        };
+        table[i] = (to_fixed(green), to_fixed(blue));
+        i += 1;
+    }
+    table
+};
+
+/// Red and green in 8.8 fixed point, every 100K from 6600K to 10000K and
+/// every 1000K from there to 40000K
+///
+/// Blue is 255 over the whole range.
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+const COOL_TABLE: [(u16, u16); COOL_LEN] = {
+    let mut table = [(0, 0); COOL_LEN];
+    let mut i = 0;
+    while i < COOL_LEN {
+        let kelvin = if i < COOL_FINE_LEN {
+            NEUTRAL_KELVIN + i as u16 * FINE_STEP
+        } else {
+            COARSE_KELVIN + (i - COOL_FINE_LEN) as u16 * COARSE_STEP
+        };
+        let temp = kelvin as f64 / 100.0 - 60.0;
+        let red = 329.698_73 * const_powf(temp, -0.133_204_76);
+        let green = 288.122_17 * const_powf(temp, -0.075_514_85);
+        table[i] = (to_fixed(red), to_fixed(green));
+        i += 1;
+    }
+    table
+};
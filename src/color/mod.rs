@@ -1,11 +1,51 @@
+/// Create an array of colors from a list of hex colors (0xRRGGBB format)
+macro_rules! hex_palette {
+    ($($color:expr),*) => {
+        [
+            $(crate::color::rgb_from_u32($color)),*
+        ]
+    };
+}
+pub(crate) use hex_palette;
+
+mod cct;
 mod gradient;
 mod kelvin;
+mod oklab;
+mod order;
+mod palette;
+mod parse;
+mod slot;
 mod utils;
+mod white;
 
-pub use gradient::{GradientDirection, fill_gradient_fp, fill_gradient_three_fp};
-pub use kelvin::kelvin_to_rgb;
-use smart_leds::{RGB8, hsv::Hsv as HSV};
+pub use cct::{CctBlend, CctConfig, ColorMix, Rgbcct, TemperatureMix, rgbcct};
+pub use gradient::{
+    ColorStop,
+    GradientDirection,
+    blend_hsv,
+    fill_gradient_fp,
+    fill_gradient_three_fp,
+    sample_stops,
+};
+pub use kelvin::{
+    kelvin_to_mired,
+    kelvin_to_rgb,
+    kelvin_to_rgb_fixed,
+    kelvin_to_rgb_float,
+    mired_to_kelvin,
+    mired_to_rgb,
+};
+pub use oklab::{Oklab, blend_oklab};
+pub use order::{CctWhiteOrder, ColorOrder, WhitePosition};
+pub use palette::{MAX_PALETTE_STOPS, PALETTE_ENTRIES, Palette};
+pub use parse::{ColorParseError, ParsedColor, format_hex, parse_color, parse_rgb};
+pub use slot::{COLOR_SLOTS, ColorSlot};
+use smart_leds::{RGB8, RGB16, hsv::Hsv as HSV};
 pub use utils::{blend_colors, hsv2rgb, mirror_half, rgb_from_u32, rgb2hsv};
+pub use white::{Rgbw, WhiteExtraction, rgbw};
 
 pub type Rgb = RGB8;
+/// 16-bit color used between the rendering and the output
+pub(crate) type Rgb16 = RGB16;
 pub type Hsv = HSV;
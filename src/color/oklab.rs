@@ -0,0 +1,136 @@
+//! Perceptual color blending in the Oklab color space
+//!
+//! Blending gamma-encoded sRGB values makes crossfades between saturated
+//! colors pass through dim, muddy tones. Converting to Oklab first keeps the
+//! perceived lightness and chroma even along the way.
+
+use crate::color::Rgb;
+
+/// Linear-light value (0-65535) of every sRGB channel value
+const SRGB_TO_LINEAR: [u16; 256] = [
+    0, 20, 40, 60, 80, 99, 119, 139, 159, 179, 199, 219, 241, 264, 288, 313, 340,
+    367, 396, 427, 458, 491, 526, 562, 599, 637, 677, 718, 761, 805, 851, 898, 947,
+    997, 1048, 1101, 1156, 1212, 1270, 1330, 1391, 1453, 1517, 1583, 1651, 1720,
+    1790, 1863, 1937, 2013, 2090, 2170, 2250, 2333, 2418, 2504, 2592, 2681, 2773,
+    2866, 2961, 3058, 3157, 3258, 3360, 3464, 3570, 3678, 3788, 3900, 4014, 4129,
+    4247, 4366, 4488, 4611, 4736, 4864, 4993, 5124, 5257, 5392, 5530, 5669, 5810,
+    5953, 6099, 6246, 6395, 6547, 6700, 6856, 7014, 7174, 7335, 7500, 7666, 7834,
+    8004, 8177, 8352, 8528, 8708, 8889, 9072, 9258, 9445, 9635, 9828, 10022, 10219,
+    10417, 10619, 10822, 11028, 11235, 11446, 11658, 11873, 12090, 12309, 12530,
+    12754, 12980, 13209, 13440, 13673, 13909, 14146, 14387, 14629, 14874, 15122,
+    15371, 15623, 15878, 16135, 16394, 16656, 16920, 17187, 17456, 17727, 18001,
+    18277, 18556, 18837, 19121, 19407, 19696, 19987, 20281, 20577, 20876, 21177,
+    21481, 21787, 22096, 22407, 22721, 23038, 23357, 23678, 24002, 24329, 24658,
+    24990, 25325, 25662, 26001, 26344, 26688, 27036, 27386, 27739, 28094, 28452,
+    28813, 29176, 29542, 29911, 30282, 30656, 31033, 31412, 31794, 32179, 32567,
+    32957, 33350, 33745, 34143, 34544, 34948, 35355, 35764, 36176, 36591, 37008,
+    37429, 37852, 38278, 38706, 39138, 39572, 40009, 40449, 40891, 41337, 41785,
+    42236, 42690, 43147, 43606, 44069, 44534, 45002, 45473, 45947, 46423, 46903,
+    47385, 47871, 48359, 48850, 49344, 49841, 50341, 50844, 51349, 51858, 52369,
+    52884, 53401, 53921, 54445, 54971, 55500, 56032, 56567, 57105, 57646, 58190,
+    58737, 59287, 59840, 60396, 60955, 61517, 62082, 62650, 63221, 63795, 64372,
+    64952, 65535,
+];
+
+/// Color in the Oklab color space
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklab {
+    /// Perceived lightness (0.0-1.0)
+    pub l: f32,
+    /// Green-red axis
+    pub a: f32,
+    /// Blue-yellow axis
+    pub b: f32,
+}
+
+impl Oklab {
+    /// Convert an sRGB color to Oklab
+    pub fn from_rgb(color: Rgb) -> Self {
+        let red = linear(color.r);
+        let green = linear(color.g);
+        let blue = linear(color.b);
+
+        // Cone responses, compressed with a cube root
+        let long = libm::cbrtf(
+            0.412_221_46 * red + 0.536_332_55 * green + 0.051_445_995 * blue,
+        );
+        let medium = libm::cbrtf(
+            0.211_903_5 * red + 0.680_699_5 * green + 0.107_396_96 * blue,
+        );
+        let short = libm::cbrtf(
+            0.088_302_46 * red + 0.281_718_85 * green + 0.629_978_7 * blue,
+        );
+
+        Self {
+            l: 0.210_454_26 * long + 0.793_617_8 * medium - 0.004_072_047 * short,
+            a: 1.977_998_5 * long - 2.428_592_2 * medium + 0.450_593_7 * short,
+            b: 0.025_904_037 * long + 0.782_771_77 * medium - 0.808_675_77 * short,
+        }
+    }
+
+    /// Convert back to sRGB, clamping colors outside the sRGB gamut
+    pub fn to_rgb(self) -> Rgb {
+        let long = self.l + 0.396_337_78 * self.a + 0.215_803_76 * self.b;
+        let medium = self.l - 0.105_561_346 * self.a - 0.063_854_17 * self.b;
+        let short = self.l - 0.089_484_18 * self.a - 1.291_485_5 * self.b;
+        let long = long * long * long;
+        let medium = medium * medium * medium;
+        let short = short * short * short;
+
+        Rgb {
+            r: encode(
+                4.076_741_7 * long - 3.307_711_6 * medium + 0.230_969_94 * short,
+            ),
+            g: encode(
+                -1.268_438 * long + 2.609_757_4 * medium - 0.341_319_38 * short,
+            ),
+            b: encode(
+                -0.004_196_086_3 * long - 0.703_418_6 * medium + 1.707_614_7 * short,
+            ),
+        }
+    }
+}
+
+/// Blend two colors in the Oklab color space
+///
+/// Drop-in replacement for [`blend_colors`](super::blend_colors) with
+/// perceptually even steps (0 = all a, 255 = all b).
+pub fn blend_oklab(a: Rgb, b: Rgb, amount_of_b: u8) -> Rgb {
+    match amount_of_b {
+        0 => return a,
+        255 => return b,
+        _ => {}
+    }
+    let t = f32::from(amount_of_b) / 255.0;
+    let from = Oklab::from_rgb(a);
+    let to = Oklab::from_rgb(b);
+    Oklab {
+        l: from.l + (to.l - from.l) * t,
+        a: from.a + (to.a - from.a) * t,
+        b: from.b + (to.b - from.b) * t,
+    }
+    .to_rgb()
+}
+
+/// Decode an sRGB channel to linear light (0.0-1.0)
+fn linear(channel: u8) -> f32 {
+    f32::from(SRGB_TO_LINEAR[usize::from(channel)]) / 65535.0
+}
+
+/// Encode linear light (0.0-1.0) to the nearest sRGB channel value
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn encode(value: f32) -> u8 {
+    let value = (value.clamp(0.0, 1.0) * 65535.0 + 0.5) as u16;
+    let above = SRGB_TO_LINEAR.partition_point(|linear| *linear < value);
+    if above == 0 {
+        return 0;
+    }
+    let above = above.min(255);
+    let below = above - 1;
+    let nearest = if value - SRGB_TO_LINEAR[below] <= SRGB_TO_LINEAR[above] - value {
+        below
+    } else {
+        above
+    };
+    nearest as u8
+}
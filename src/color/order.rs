@@ -0,0 +1,102 @@
+//! Color channel order of LED strips
+//!
+//! Strips expect the channels in different orders on the wire: WS2812 is
+//! GRB, some SK6812 batches are RGB and some clones are BRG. Frames are
+//! rendered in RGB and reordered by the output adapters, after the white
+//! channels were split off.
+
+use smart_leds::White;
+
+use crate::color::{Rgb, Rgbcct, Rgbw, rgbcct, rgbw};
+
+/// Order of the color channels on the wire
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorOrder {
+    #[default]
+    Rgb,
+    Rbg,
+    Grb,
+    Gbr,
+    Brg,
+    Bgr,
+}
+
+impl ColorOrder {
+    /// Reorder the channels of a color
+    ///
+    /// The `r`, `g` and `b` fields of the result hold the first, second and
+    /// third channel sent to the strip.
+    pub const fn apply(self, color: Rgb) -> Rgb {
+        let Rgb { r, g, b } = color;
+        let (first, second, third) = match self {
+            Self::Rgb => (r, g, b),
+            Self::Rbg => (r, b, g),
+            Self::Grb => (g, r, b),
+            Self::Gbr => (g, b, r),
+            Self::Brg => (b, r, g),
+            Self::Bgr => (b, g, r),
+        };
+        Rgb {
+            r: first,
+            g: second,
+            b: third,
+        }
+    }
+
+    /// Reorder the channels of an RGBW pixel, placing W at `white`
+    ///
+    /// The `r`, `g`, `b` and `a` fields of the result hold the first to
+    /// fourth channel sent to the strip.
+    pub const fn apply_rgbw(self, color: Rgbw, white: WhitePosition) -> Rgbw {
+        let rgb = self.apply(Rgb {
+            r: color.r,
+            g: color.g,
+            b: color.b,
+        });
+        match white {
+            WhitePosition::First => Rgbw {
+                r: color.a.0,
+                g: rgb.r,
+                b: rgb.g,
+                a: White(rgb.b),
+            },
+            WhitePosition::Last => rgbw(rgb, color.a.0),
+        }
+    }
+
+    /// Reorder the channels of an RGB+CCT pixel, whites in `whites` order
+    ///
+    /// The whites are sent after the color channels. The `cold` and `warm`
+    /// fields of the result hold the fourth and fifth channel.
+    pub const fn apply_rgbcct(self, color: Rgbcct, whites: CctWhiteOrder) -> Rgbcct {
+        let rgb = self.apply(Rgb {
+            r: color.r,
+            g: color.g,
+            b: color.b,
+        });
+        match whites {
+            CctWhiteOrder::ColdWarm => rgbcct(rgb, color.a.warm, color.a.cold),
+            CctWhiteOrder::WarmCold => rgbcct(rgb, color.a.cold, color.a.warm),
+        }
+    }
+}
+
+/// Position of the W channel on RGBW strips
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WhitePosition {
+    /// W is sent before the color channels
+    First,
+    /// W is sent after the color channels
+    #[default]
+    Last,
+}
+
+/// Order of the two white channels on RGB+CCT strips
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CctWhiteOrder {
+    /// Cold white is sent before warm white
+    #[default]
+    ColdWarm,
+    /// Warm white is sent before cold white
+    WarmCold,
+}
@@ -0,0 +1,196 @@
+//! Color palettes
+//!
+//! A [`Palette`] maps an index (0-255) to a color. Palettes are either 16
+//! evenly spaced entries that wrap around, like `FastLED` palettes, or a list
+//! of gradient stops.
+
+use heapless::Vec;
+
+use crate::{
+    color::{ColorStop, Rgb, blend_colors, sample_stops},
+    math8::scale8,
+};
+
+/// Number of entries in an entry palette
+pub const PALETTE_ENTRIES: usize = 16;
+/// Maximum number of stops in a gradient-stop palette
+pub const MAX_PALETTE_STOPS: usize = 16;
+
+/// Color palette indexed by 0-255
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Palette {
+    /// 16 evenly spaced entries, the last one blends back into the first
+    Entries([Rgb; PALETTE_ENTRIES]),
+    /// Gradient stops sorted by position, the end colors extend to the edges
+    Stops(Vec<ColorStop, MAX_PALETTE_STOPS>),
+    /// Gradient stops in static memory, like [`Self::Stops`]
+    StaticStops(&'static [ColorStop]),
+}
+
+/// Position of stop `i` of `last + 1` evenly spread stops
+#[allow(clippy::cast_possible_truncation)]
+const fn spread_position(i: usize, last: usize) -> u8 {
+    match (i * 255).checked_div(last) {
+        Some(position) => position as u8,
+        None => 0,
+    }
+}
+
+#[allow(clippy::unreadable_literal)]
+impl Palette {
+    /// Black through red and yellow to white
+    pub const HEAT: Self = Self::Entries(hex_palette![
+        0x000000, 0x330000, 0x660000, 0x990000, 0xCC0000, 0xFF0000, 0xFF3300,
+        0xFF6600, 0xFF9900, 0xFFCC00, 0xFFFF00, 0xFFFF33, 0xFFFF66, 0xFFFF99,
+        0xFFFFCC, 0xFFFFFF
+    ]);
+
+    /// Deep blues, teals and aqua
+    pub const OCEAN: Self = Self::Entries(hex_palette![
+        0x191970, 0x00008B, 0x191970, 0x000080, 0x00008B, 0x0000CD, 0x2E8B57,
+        0x008080, 0x5F9EA0, 0x0000FF, 0x008B8B, 0x6495ED, 0x7FFFD4, 0x2E8B57,
+        0x00FFFF, 0x87CEFA
+    ]);
+
+    /// Greens from dark olive to lawn green
+    pub const FOREST: Self = Self::Entries(hex_palette![
+        0x006400, 0x006400, 0x556B2F, 0x006400, 0x008000, 0x228B22, 0x6B8E23,
+        0x008000, 0x2E8B57, 0x66CDAA, 0x32CD32, 0x9ACD32, 0x90EE90, 0x7CFC00,
+        0x66CDAA, 0x228B22
+    ]);
+
+    /// Purples, reds and yellows without green
+    pub const PARTY: Self = Self::Entries(hex_palette![
+        0x5500AB, 0x84007C, 0xB5004B, 0xE5001B, 0xE81700, 0xB84700, 0xAB7700,
+        0xABAB00, 0xAB5500, 0xDD2200, 0xF2000E, 0xC2003E, 0x8F0071, 0x5F00A1,
+        0x2F00D0, 0x0007F9
+    ]);
+
+    /// Blues with sky blue and white highlights
+    pub const CLOUD: Self = Self::Entries(hex_palette![
+        0x0000FF, 0x00008B, 0x00008B, 0x00008B, 0x00008B, 0x00008B, 0x00008B,
+        0x00008B, 0x0000FF, 0x00008B, 0x87CEEB, 0x87CEEB, 0xADD8E6, 0xFFFFFF,
+        0xADD8E6, 0x87CEEB
+    ]);
+
+    /// Dark reds with orange and white flares
+    pub const LAVA: Self = Self::Entries(hex_palette![
+        0x000000, 0x800000, 0x000000, 0x800000, 0x8B0000, 0x8B0000, 0x800000,
+        0x8B0000, 0x8B0000, 0x8B0000, 0xFF0000, 0xFFA500, 0xFFFFFF, 0xFFA500,
+        0xFF0000, 0x8B0000
+    ]);
+
+    /// Full hue circle
+    pub const RAINBOW: Self = Self::Entries(hex_palette![
+        0xFF0000, 0xD52A00, 0xAB5500, 0xAB7F00, 0xABAB00, 0x56D500, 0x00FF00,
+        0x00D52A, 0x00AB55, 0x0056AA, 0x0000FF, 0x2A00D5, 0x5500AB, 0x7F0081,
+        0xAB0055, 0xD5002B
+    ]);
+
+    /// Create a gradient-stop palette
+    ///
+    /// Stops are sorted by position. Returns `None` unless there are 1 to 16
+    /// stops.
+    pub fn from_stops(stops: &[ColorStop]) -> Option<Self> {
+        if stops.is_empty() {
+            return None;
+        }
+        let mut stops =
+            Vec::<ColorStop, MAX_PALETTE_STOPS>::from_slice(stops).ok()?;
+        stops.sort_unstable_by_key(|stop| stop.position);
+        Some(Self::Stops(stops))
+    }
+
+    /// Create a gradient-stop palette with colors spread evenly from start
+    /// to end
+    ///
+    /// Returns `None` unless there are 1 to 16 colors.
+    pub fn from_colors(colors: &[Rgb]) -> Option<Self> {
+        if colors.len() > MAX_PALETTE_STOPS {
+            return None;
+        }
+        let last = colors.len().checked_sub(1)?;
+        let stops = colors
+            .iter()
+            .enumerate()
+            .map(|(i, color)| ColorStop::new(spread_position(i, last), *color))
+            .collect();
+        Some(Self::Stops(stops))
+    }
+
+    /// Spread colors evenly from start to end, as [`Self::from_colors`] does
+    ///
+    /// Usable in const context to build [`Self::StaticStops`] palettes.
+    pub const fn spread_stops<const N: usize>(colors: [Rgb; N]) -> [ColorStop; N] {
+        let mut stops = [ColorStop::new(0, Rgb { r: 0, g: 0, b: 0 }); N];
+        let mut i = 0;
+        while i < N {
+            stops[i] =
+                ColorStop::new(spread_position(i, N.saturating_sub(1)), colors[i]);
+            i += 1;
+        }
+        stops
+    }
+
+    /// Get the number of entries or stops
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Entries(entries) => entries.len(),
+            Self::Stops(stops) => stops.len(),
+            Self::StaticStops(stops) => stops.len(),
+        }
+    }
+
+    /// Check if the palette has no colors
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Look up the color at `index` (0-255), scaled by `brightness`
+    ///
+    /// Without `blend`, the color of the entry or stop at or before `index`
+    /// is returned as is.
+    pub fn color_from_palette(&self, index: u8, brightness: u8, blend: bool) -> Rgb {
+        let color = match self {
+            Self::Entries(entries) => {
+                let entry = usize::from(index >> 4);
+                let amount = (index & 0x0F) << 4;
+                let color = entries[entry];
+                if blend && amount > 0 {
+                    let next = entries[(entry + 1) % PALETTE_ENTRIES];
+                    blend_colors(color, next, amount)
+                } else {
+                    color
+                }
+            }
+            Self::Stops(stops) => Self::stop_color(stops, index, blend),
+            Self::StaticStops(stops) => Self::stop_color(stops, index, blend),
+        };
+        if brightness == 255 {
+            return color;
+        }
+        Rgb {
+            r: scale8(color.r, brightness),
+            g: scale8(color.g, brightness),
+            b: scale8(color.b, brightness),
+        }
+    }
+
+    /// Color of sorted gradient stops at `index`
+    fn stop_color(stops: &[ColorStop], index: u8, blend: bool) -> Rgb {
+        if blend {
+            return sample_stops(stops, index, false);
+        }
+        stops
+            .iter()
+            .rev()
+            .find(|stop| stop.position <= index)
+            .or(stops.first())
+            .map_or(Rgb { r: 0, g: 0, b: 0 }, |stop| stop.color)
+    }
+
+    /// Sample the palette at `index` (0-255) with blending
+    pub fn sample(&self, index: u8) -> Rgb {
+        self.color_from_palette(index, 255, true)
+    }
+}
@@ -0,0 +1,386 @@
+//! Color string parsing and formatting
+//!
+//! Parses the color notations used by consoles and home automation
+//! messages: hex (`#ff8800`, `ff8800`, `#f80`), CSS color names,
+//! `rgb(255, 136, 0)`, `hsv(30, 100%, 100%)` and color temperatures
+//! (`2700K`). Everything works on `&str` without allocation.
+
+use core::str::FromStr;
+
+use heapless::String;
+
+use crate::color::{Hsv, Rgb, hsv2rgb, rgb_from_u32};
+
+/// Lowest color temperature accepted in Kelvin
+const MIN_KELVIN: u16 = 1_000;
+/// Highest color temperature accepted in Kelvin
+const MAX_KELVIN: u16 = 40_000;
+
+/// Color parsed from a string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParsedColor {
+    /// RGB color
+    Rgb(Rgb),
+    /// Color temperature in Kelvin
+    Kelvin(u16),
+}
+
+/// Error returned when a color string can not be parsed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorParseError {
+    /// The string is empty
+    Empty,
+    /// A hex color has the wrong length or a non-hex digit
+    InvalidHex,
+    /// The string is not a known color name
+    UnknownName,
+    /// A function is unknown, misses a parenthesis or has the wrong number
+    /// of components
+    InvalidSyntax,
+    /// A component is not a number
+    InvalidNumber,
+    /// A component or temperature is out of its range
+    OutOfRange,
+}
+
+impl FromStr for ParsedColor {
+    type Err = ColorParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_color(input)
+    }
+}
+
+/// Parse a color string
+///
+/// Accepts `#rrggbb`, `rrggbb`, `#rgb`, CSS color names,
+/// `rgb(r, g, b)` with components 0-255, `hsv(h, s%, v%)` with the hue in
+/// degrees and saturation and value in percent, and temperatures like
+/// `2700K` between 1000K and 40000K. Names and functions are case
+/// insensitive, surrounding whitespace is ignored.
+pub fn parse_color(input: &str) -> Result<ParsedColor, ColorParseError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(ColorParseError::Empty);
+    }
+
+    if let Some(hex) = input.strip_prefix('#') {
+        return parse_hex(hex).map(ParsedColor::Rgb);
+    }
+    if let Some(args) = function_args(input, "rgb")? {
+        return parse_rgb_args(args).map(ParsedColor::Rgb);
+    }
+    if let Some(args) = function_args(input, "hsv")? {
+        return parse_hsv_args(args).map(ParsedColor::Rgb);
+    }
+    if let Some(kelvin) = input
+        .strip_suffix('K')
+        .or_else(|| input.strip_suffix('k'))
+        .filter(|digits| is_number(digits))
+    {
+        return parse_kelvin(kelvin).map(ParsedColor::Kelvin);
+    }
+    if input.len() == 6 && input.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return parse_hex(input).map(ParsedColor::Rgb);
+    }
+    named_color(input)
+        .map(ParsedColor::Rgb)
+        .ok_or(ColorParseError::UnknownName)
+}
+
+/// Parse a color string that must be an RGB color
+///
+/// Same as [`parse_color`], but temperatures are converted to RGB with
+/// [`kelvin_to_rgb`](crate::color::kelvin_to_rgb).
+pub fn parse_rgb(input: &str) -> Result<Rgb, ColorParseError> {
+    match parse_color(input)? {
+        ParsedColor::Rgb(color) => Ok(color),
+        ParsedColor::Kelvin(kelvin) => Ok(crate::color::kelvin_to_rgb(kelvin)),
+    }
+}
+
+/// Format a color as a `#rrggbb` hex string
+pub fn format_hex(color: Rgb) -> String<7> {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    let mut hex = String::new();
+    let _ = hex.push('#');
+    for channel in [color.r, color.g, color.b] {
+        let _ = hex.push(char::from(DIGITS[usize::from(channel >> 4)]));
+        let _ = hex.push(char::from(DIGITS[usize::from(channel & 0x0F)]));
+    }
+    hex
+}
+
+/// Parse `rrggbb` or `rgb` hex digits
+#[allow(clippy::cast_possible_truncation)]
+fn parse_hex(hex: &str) -> Result<Rgb, ColorParseError> {
+    if !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(ColorParseError::InvalidHex);
+    }
+    let value =
+        u32::from_str_radix(hex, 16).map_err(|_| ColorParseError::InvalidHex)?;
+    match hex.len() {
+        6 => Ok(rgb_from_u32(value)),
+        // Each digit is doubled, `f80` is `ff8800`
+        3 => {
+            let digit = |shift: u32| ((value >> shift) & 0xF) as u8 * 0x11;
+            Ok(Rgb {
+                r: digit(8),
+                g: digit(4),
+                b: digit(0),
+            })
+        }
+        _ => Err(ColorParseError::InvalidHex),
+    }
+}
+
+/// Get the arguments of `name(...)`, or `None` if `input` is not a call of
+/// `name`
+fn function_args<'a>(
+    input: &'a str,
+    name: &str,
+) -> Result<Option<&'a str>, ColorParseError> {
+    let Some(prefix) = input.get(..name.len()) else {
+        return Ok(None);
+    };
+    if !prefix.eq_ignore_ascii_case(name) {
+        return Ok(None);
+    }
+    let rest = input[name.len()..].trim_start();
+    let Some(rest) = rest.strip_prefix('(') else {
+        return Ok(None);
+    };
+    rest.strip_suffix(')')
+        .map(Some)
+        .ok_or(ColorParseError::InvalidSyntax)
+}
+
+/// Split function arguments into exactly three components
+fn components(args: &str) -> Result<[&str; 3], ColorParseError> {
+    let mut parts = args.split(',').map(str::trim);
+    let (Some(first), Some(second), Some(third), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(ColorParseError::InvalidSyntax);
+    };
+    Ok([first, second, third])
+}
+
+/// Check if a string is a non-empty run of decimal digits
+fn is_number(value: &str) -> bool {
+    !value.is_empty() && value.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+/// Parse a decimal component no greater than `max`
+fn parse_component(value: &str, max: u16) -> Result<u16, ColorParseError> {
+    if !is_number(value) {
+        return Err(ColorParseError::InvalidNumber);
+    }
+    match value.parse::<u16>() {
+        Ok(value) if value <= max => Ok(value),
+        _ => Err(ColorParseError::OutOfRange),
+    }
+}
+
+/// Parse the components of `rgb(r, g, b)`
+#[allow(clippy::cast_possible_truncation)]
+fn parse_rgb_args(args: &str) -> Result<Rgb, ColorParseError> {
+    let [r, g, b] = components(args)?;
+    Ok(Rgb {
+        r: parse_component(r, 255)? as u8,
+        g: parse_component(g, 255)? as u8,
+        b: parse_component(b, 255)? as u8,
+    })
+}
+
+/// Parse the components of `hsv(h, s%, v%)`
+#[allow(clippy::cast_possible_truncation)]
+fn parse_hsv_args(args: &str) -> Result<Rgb, ColorParseError> {
+    let [hue, sat, val] = components(args)?;
+    let percent = |value: &str| -> Result<u8, ColorParseError> {
+        let value = value.strip_suffix('%').unwrap_or(value).trim_end();
+        let percent = parse_component(value, 100)?;
+        Ok(((percent * 255 + 50) / 100) as u8)
+    };
+    let degrees = u32::from(parse_component(hue, 360)?);
+    Ok(hsv2rgb(Hsv {
+        hue: ((degrees * 256 + 180) / 360 % 256) as u8,
+        sat: percent(sat)?,
+        val: percent(val)?,
+    }))
+}
+
+/// Parse the digits of `2700K`
+fn parse_kelvin(digits: &str) -> Result<u16, ColorParseError> {
+    let kelvin = parse_component(digits, MAX_KELVIN)?;
+    if kelvin < MIN_KELVIN {
+        return Err(ColorParseError::OutOfRange);
+    }
+    Ok(kelvin)
+}
+
+/// Look up a CSS color name, ignoring case
+fn named_color(name: &str) -> Option<Rgb> {
+    NAMED_COLORS
+        .binary_search_by(|(known, _)| {
+            let known = known.bytes();
+            let name = name.bytes().map(|byte| byte.to_ascii_lowercase());
+            known.cmp(name)
+        })
+        .ok()
+        .map(|index| rgb_from_u32(NAMED_COLORS[index].1))
+}
+
+/// CSS color names sorted by name
+#[allow(clippy::unreadable_literal)]
+const NAMED_COLORS: [(&str, u32); 148] = [
+    ("aliceblue", 0xF0F8FF),
+    ("antiquewhite", 0xFAEBD7),
+    ("aqua", 0x00FFFF),
+    ("aquamarine", 0x7FFFD4),
+    ("azure", 0xF0FFFF),
+    ("beige", 0xF5F5DC),
+    ("bisque", 0xFFE4C4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xFFEBCD),
+    ("blue", 0x0000FF),
+    ("blueviolet", 0x8A2BE2),
+    ("brown", 0xA52A2A),
+    ("burlywood", 0xDEB887),
+    ("cadetblue", 0x5F9EA0),
+    ("chartreuse", 0x7FFF00),
+    ("chocolate", 0xD2691E),
+    ("coral", 0xFF7F50),
+    ("cornflowerblue", 0x6495ED),
+    ("cornsilk", 0xFFF8DC),
+    ("crimson", 0xDC143C),
+    ("cyan", 0x00FFFF),
+    ("darkblue", 0x00008B),
+    ("darkcyan", 0x008B8B),
+    ("darkgoldenrod", 0xB8860B),
+    ("darkgray", 0xA9A9A9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xA9A9A9),
+    ("darkkhaki", 0xBDB76B),
+    ("darkmagenta", 0x8B008B),
+    ("darkolivegreen", 0x556B2F),
+    ("darkorange", 0xFF8C00),
+    ("darkorchid", 0x9932CC),
+    ("darkred", 0x8B0000),
+    ("darksalmon", 0xE9967A),
+    ("darkseagreen", 0x8FBC8F),
+    ("darkslateblue", 0x483D8B),
+    ("darkslategray", 0x2F4F4F),
+    ("darkslategrey", 0x2F4F4F),
+    ("darkturquoise", 0x00CED1),
+    ("darkviolet", 0x9400D3),
+    ("deeppink", 0xFF1493),
+    ("deepskyblue", 0x00BFFF),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1E90FF),
+    ("firebrick", 0xB22222),
+    ("floralwhite", 0xFFFAF0),
+    ("forestgreen", 0x228B22),
+    ("fuchsia", 0xFF00FF),
+    ("gainsboro", 0xDCDCDC),
+    ("ghostwhite", 0xF8F8FF),
+    ("gold", 0xFFD700),
+    ("goldenrod", 0xDAA520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xADFF2F),
+    ("grey", 0x808080),
+    ("honeydew", 0xF0FFF0),
+    ("hotpink", 0xFF69B4),
+    ("indianred", 0xCD5C5C),
+    ("indigo", 0x4B0082),
+    ("ivory", 0xFFFFF0),
+    ("khaki", 0xF0E68C),
+    ("lavender", 0xE6E6FA),
+    ("lavenderblush", 0xFFF0F5),
+    ("lawngreen", 0x7CFC00),
+    ("lemonchiffon", 0xFFFACD),
+    ("lightblue", 0xADD8E6),
+    ("lightcoral", 0xF08080),
+    ("lightcyan", 0xE0FFFF),
+    ("lightgoldenrodyellow", 0xFAFAD2),
+    ("lightgray", 0xD3D3D3),
+    ("lightgreen", 0x90EE90),
+    ("lightgrey", 0xD3D3D3),
+    ("lightpink", 0xFFB6C1),
+    ("lightsalmon", 0xFFA07A),
+    ("lightseagreen", 0x20B2AA),
+    ("lightskyblue", 0x87CEFA),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xB0C4DE),
+    ("lightyellow", 0xFFFFE0),
+    ("lime", 0x00FF00),
+    ("limegreen", 0x32CD32),
+    ("linen", 0xFAF0E6),
+    ("magenta", 0xFF00FF),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66CDAA),
+    ("mediumblue", 0x0000CD),
+    ("mediumorchid", 0xBA55D3),
+    ("mediumpurple", 0x9370DB),
+    ("mediumseagreen", 0x3CB371),
+    ("mediumslateblue", 0x7B68EE),
+    ("mediumspringgreen", 0x00FA9A),
+    ("mediumturquoise", 0x48D1CC),
+    ("mediumvioletred", 0xC71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xF5FFFA),
+    ("mistyrose", 0xFFE4E1),
+    ("moccasin", 0xFFE4B5),
+    ("navajowhite", 0xFFDEAD),
+    ("navy", 0x000080),
+    ("oldlace", 0xFDF5E6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6B8E23),
+    ("orange", 0xFFA500),
+    ("orangered", 0xFF4500),
+    ("orchid", 0xDA70D6),
+    ("palegoldenrod", 0xEEE8AA),
+    ("palegreen", 0x98FB98),
+    ("paleturquoise", 0xAFEEEE),
+    ("palevioletred", 0xDB7093),
+    ("papayawhip", 0xFFEFD5),
+    ("peachpuff", 0xFFDAB9),
+    ("peru", 0xCD853F),
+    ("pink", 0xFFC0CB),
+    ("plum", 0xDDA0DD),
+    ("powderblue", 0xB0E0E6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xFF0000),
+    ("rosybrown", 0xBC8F8F),
+    ("royalblue", 0x4169E1),
+    ("saddlebrown", 0x8B4513),
+    ("salmon", 0xFA8072),
+    ("sandybrown", 0xF4A460),
+    ("seagreen", 0x2E8B57),
+    ("seashell", 0xFFF5EE),
+    ("sienna", 0xA0522D),
+    ("silver", 0xC0C0C0),
+    ("skyblue", 0x87CEEB),
+    ("slateblue", 0x6A5ACD),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xFFFAFA),
+    ("springgreen", 0x00FF7F),
+    ("steelblue", 0x4682B4),
+    ("tan", 0xD2B48C),
+    ("teal", 0x008080),
+    ("thistle", 0xD8BFD8),
+    ("tomato", 0xFF6347),
+    ("turquoise", 0x40E0D0),
+    ("violet", 0xEE82EE),
+    ("wheat", 0xF5DEB3),
+    ("white", 0xFFFFFF),
+    ("whitesmoke", 0xF5F5F5),
+    ("yellow", 0xFFFF00),
+    ("yellowgreen", 0x9ACD32),
+];
@@ -0,0 +1,38 @@
+/// Number of user color slots
+pub const COLOR_SLOTS: usize = 3;
+
+/// Index of a user color
+///
+/// Single-color effects only read the primary color. Multi-color effects
+/// use the other slots for accents or backgrounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ColorSlot {
+    /// Main color, used by single-color effects
+    Primary   = 0,
+    /// Second color
+    Secondary = 1,
+    /// Third color
+    Tertiary  = 2,
+}
+
+impl ColorSlot {
+    /// All slots in index order
+    pub const ALL: [Self; COLOR_SLOTS] =
+        [Self::Primary, Self::Secondary, Self::Tertiary];
+
+    /// Get the slot for an index
+    pub const fn from_index(index: usize) -> Option<Self> {
+        match index {
+            0 => Some(Self::Primary),
+            1 => Some(Self::Secondary),
+            2 => Some(Self::Tertiary),
+            _ => None,
+        }
+    }
+
+    /// Get the index of the slot
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+}
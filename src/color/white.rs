@@ -0,0 +1,112 @@
+//! White channel extraction for RGBW strips
+//!
+//! Effects render RGB colors. Strips with a white LED (e.g. SK6812 RGBW) get
+//! the white part of every color moved to the W channel at output, which is
+//! brighter and has a better color rendering than mixing white from RGB.
+
+use smart_leds::{RGBW, White};
+
+use crate::{
+    color::{Rgb, kelvin_to_rgb},
+    math8::scale8,
+};
+
+/// RGBW pixel
+pub type Rgbw = RGBW<u8>;
+
+/// Create an RGBW pixel from a color and a white level
+pub const fn rgbw(color: Rgb, white: u8) -> Rgbw {
+    Rgbw {
+        r: color.r,
+        g: color.g,
+        b: color.b,
+        a: White(white),
+    }
+}
+
+/// How the W channel is derived from RGB colors
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WhiteExtraction {
+    /// Keep the W channel off
+    Off,
+    /// Move the common part of the channels to W
+    #[default]
+    MinSubtract,
+    /// Move as much as possible of the white LED's own color to W
+    ///
+    /// Holds the RGB color of the white LED, see [`Self::white_led`]. Colors
+    /// matching the white LED end up on the W channel alone, so color
+    /// temperature changes drive W directly and only the difference to the
+    /// white LED is mixed from RGB.
+    WhiteLed(Rgb),
+}
+
+impl WhiteExtraction {
+    /// Match the color of a white LED with the given color temperature
+    pub fn white_led(kelvin: u16) -> Self {
+        Self::WhiteLed(kelvin_to_rgb(kelvin))
+    }
+
+    /// Split a color into RGB and W channels
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn to_rgbw(self, color: Rgb) -> Rgbw {
+        match self {
+            Self::Off => rgbw(color, 0),
+            Self::MinSubtract => {
+                let white = color.r.min(color.g).min(color.b);
+                rgbw(
+                    Rgb {
+                        r: color.r - white,
+                        g: color.g - white,
+                        b: color.b - white,
+                    },
+                    white,
+                )
+            }
+            Self::WhiteLed(led) => {
+                // Highest W level whose light fits into every channel
+                let fit = |channel: u8, led: u8| {
+                    if led == 0 {
+                        255
+                    } else {
+                        (u16::from(channel) * 255 / u16::from(led)).min(255)
+                    }
+                };
+                let white = fit(color.r, led.r)
+                    .min(fit(color.g, led.g))
+                    .min(fit(color.b, led.b));
+                let used = |led: u8| (u16::from(led) * white / 255) as u8;
+                rgbw(
+                    Rgb {
+                        r: color.r.saturating_sub(used(led.r)),
+                        g: color.g.saturating_sub(used(led.g)),
+                        b: color.b.saturating_sub(used(led.b)),
+                    },
+                    white as u8,
+                )
+            }
+        }
+    }
+
+    /// Split a color that shows a color temperature into RGB and W channels
+    ///
+    /// The W channel follows the brightest channel of the color, like the
+    /// whites of RGB+CCT strips. With [`Self::WhiteLed`] the RGB channels add
+    /// what the white LED lacks of the color, with [`Self::MinSubtract`] they
+    /// stay off. [`Self::Off`] keeps the color on RGB.
+    pub fn temperature_to_rgbw(self, color: Rgb) -> Rgbw {
+        let level = color.r.max(color.g).max(color.b);
+        match self {
+            Self::Off => rgbw(color, 0),
+            Self::MinSubtract => rgbw(Rgb { r: 0, g: 0, b: 0 }, level),
+            Self::WhiteLed(led) => rgbw(
+                Rgb {
+                    r: color.r.saturating_sub(scale8(led.r, level)),
+                    g: color.g.saturating_sub(scale8(led.g, level)),
+                    b: color.b.saturating_sub(scale8(led.b, level)),
+                },
+                level,
+            ),
+        }
+    }
+}
@@ -0,0 +1,200 @@
+//! Circadian white effect
+//!
+//! Follows a daily curve of color temperature and brightness: cool white
+//! around noon, warm and dim at night. The time of day is supplied by the
+//! caller through [`DayClock`].
+
+use embassy_time::Instant;
+use heapless::Vec;
+
+use super::Effect;
+use crate::{
+    color::{Rgb, kelvin_to_rgb},
+    math8::{blend8, scale8},
+    time_of_day::{DayClock, TimeOfDay},
+};
+
+/// Maximum number of points in a circadian curve
+pub const MAX_CIRCADIAN_POINTS: usize = 8;
+
+const MINUTES_PER_DAY: u16 = 24 * 60;
+
+/// A single point of the circadian curve
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircadianPoint {
+    /// Minutes since midnight (0-1439)
+    pub minute: u16,
+    /// Color temperature in Kelvin
+    pub kelvin: u16,
+    /// Brightness (0-255)
+    pub brightness: u8,
+}
+
+impl CircadianPoint {
+    /// Create a new curve point at `hour:minute`
+    pub const fn new(hour: u8, minute: u8, kelvin: u16, brightness: u8) -> Self {
+        Self {
+            minute: hour as u16 * 60 + minute as u16,
+            kelvin,
+            brightness,
+        }
+    }
+}
+
+/// Daily color temperature and brightness curve
+///
+/// Points are kept sorted by time of day. Values between points are linearly
+/// interpolated, wrapping around midnight.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircadianCurve {
+    points: Vec<CircadianPoint, MAX_CIRCADIAN_POINTS>,
+}
+
+impl Default for CircadianCurve {
+    fn default() -> Self {
+        Self::from_points(&[
+            CircadianPoint::new(0, 0, 2000, 40),
+            CircadianPoint::new(6, 0, 2200, 60),
+            CircadianPoint::new(9, 0, 4000, 220),
+            CircadianPoint::new(12, 0, 6000, 255),
+            CircadianPoint::new(17, 0, 4500, 230),
+            CircadianPoint::new(20, 0, 2700, 150),
+            CircadianPoint::new(22, 0, 2200, 80),
+        ])
+    }
+}
+
+impl CircadianCurve {
+    /// Create a curve from a list of points
+    ///
+    /// Points may be given in any order. Extra points beyond
+    /// [`MAX_CIRCADIAN_POINTS`] are ignored.
+    pub fn from_points(points: &[CircadianPoint]) -> Self {
+        let mut sorted = Vec::new();
+        for point in points.iter().take(MAX_CIRCADIAN_POINTS) {
+            let mut point = *point;
+            point.minute %= MINUTES_PER_DAY;
+            let _ = sorted.push(point);
+        }
+        sorted.sort_unstable_by_key(|point: &CircadianPoint| point.minute);
+        Self { points: sorted }
+    }
+
+    /// Get the curve points, sorted by time of day
+    pub fn points(&self) -> &[CircadianPoint] {
+        &self.points
+    }
+
+    /// Sample the curve at the given time of day
+    ///
+    /// Returns `(kelvin, brightness)`. An empty curve yields neutral white at
+    /// full brightness.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn sample(&self, time: TimeOfDay) -> (u16, u8) {
+        let (Some(first), Some(last)) = (self.points.first(), self.points.last())
+        else {
+            return (6500, 255);
+        };
+
+        let minute_ms = u32::from(time.as_minutes()) * 60_000;
+        let fraction = time.as_millis() - minute_ms;
+        let now = u32::from(time.as_minutes());
+
+        // Find the segment containing `now`, wrapping around midnight
+        let next_index = self
+            .points
+            .iter()
+            .position(|point| u32::from(point.minute) > now);
+        let (from, to) = match next_index {
+            Some(0) | None => (*last, *first),
+            Some(index) => (self.points[index - 1], self.points[index]),
+        };
+
+        let day = u32::from(MINUTES_PER_DAY);
+        let from_minute = u32::from(from.minute);
+        let span = (u32::from(to.minute) + day - from_minute) % day;
+        if span == 0 {
+            return (from.kelvin, from.brightness);
+        }
+        let offset = (now + day - from_minute) % day;
+        let offset_ms = u64::from(offset * 60_000 + fraction);
+        let span_ms = u64::from(span * 60_000);
+        let progress = ((offset_ms * 255) / span_ms).min(255) as u8;
+
+        let kelvin = i32::from(from.kelvin)
+            + (i32::from(to.kelvin) - i32::from(from.kelvin)) * i32::from(progress)
+                / 255;
+        #[allow(clippy::cast_sign_loss)]
+        let kelvin = kelvin as u16;
+        let brightness = blend8(from.brightness, to.brightness, progress);
+
+        (kelvin, brightness)
+    }
+}
+
+/// Circadian white effect
+///
+/// Renders white light whose color temperature and brightness follow a
+/// [`CircadianCurve`] over the day.
+#[derive(Debug, Clone)]
+pub struct CircadianEffect {
+    curve: CircadianCurve,
+    clock: DayClock,
+}
+
+impl Default for CircadianEffect {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CircadianEffect {
+    /// Create a new circadian effect with the default curve
+    ///
+    /// Until a clock is set, `Instant` zero is treated as midnight.
+    pub fn new() -> Self {
+        Self {
+            curve: CircadianCurve::default(),
+            clock: DayClock::default(),
+        }
+    }
+
+    /// Set the daily curve
+    #[must_use]
+    pub fn with_curve(mut self, curve: CircadianCurve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    /// Replace the daily curve
+    pub fn set_curve(&mut self, curve: CircadianCurve) {
+        self.curve = curve;
+    }
+
+    /// Set the clock used to derive the time of day
+    pub fn set_clock(&mut self, clock: DayClock) {
+        self.clock = clock;
+    }
+
+    /// Get the color for the given time of day
+    pub fn color_at(&self, time: TimeOfDay) -> Rgb {
+        let (kelvin, brightness) = self.curve.sample(time);
+        let white = kelvin_to_rgb(kelvin);
+        Rgb {
+            r: scale8(white.r, brightness),
+            g: scale8(white.g, brightness),
+            b: scale8(white.b, brightness),
+        }
+    }
+}
+
+impl Effect for CircadianEffect {
+    const PRECISE_COLORS: bool = true;
+
+    fn render(&mut self, now: Instant, leds: &mut [Rgb]) {
+        let color = self.color_at(self.clock.at(now));
+        for led in leds {
+            *led = color;
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! Analog clock on an LED ring
+//!
+//! Shows hours, minutes and seconds as soft colored markers. The seconds
+//! marker sweeps smoothly. Time of day comes from the caller through
+//! [`DayClock`].
+
+use embassy_time::{Duration, Instant};
+
+use super::Effect;
+use crate::{
+    bounds::{RING_TURN, ring_distance, ring_position},
+    color::Rgb,
+    math8::scale8,
+    time_of_day::{DayClock, MS_PER_DAY},
+    transition::{ColorBlending, ValueTransition},
+};
+
+/// Half width of the hour hand in 8.8 fixed-point LEDs
+const HOUR_WIDTH: u32 = 2 << 8;
+/// Half width of the minute hand in 8.8 fixed-point LEDs
+const MINUTE_WIDTH: u32 = 3 << 7;
+/// Half width of the second hand in 8.8 fixed-point LEDs
+const SECOND_WIDTH: u32 = 1 << 8;
+
+const MS_PER_HALF_DAY: u32 = MS_PER_DAY / 2;
+const MS_PER_HOUR: u32 = 60 * 60 * 1000;
+const MS_PER_MINUTE: u32 = 60 * 1000;
+
+/// Colors and ring layout of the clock
+///
+/// In the renderer, colors set in the color slots after the style replace
+/// the hand colors, see [`Self::set_color_at`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockStyle {
+    /// Hour hand color
+    pub hour: Rgb,
+    /// Minute hand color
+    pub minute: Rgb,
+    /// Second hand color, `None` hides the second hand
+    pub second: Option<Rgb>,
+    /// Background color
+    pub background: Rgb,
+    /// Index of the LED at 12 o'clock, relative to the rendering bounds
+    pub top_offset: u8,
+    /// LED indices run counter-clockwise
+    pub reversed: bool,
+}
+
+impl Default for ClockStyle {
+    fn default() -> Self {
+        Self {
+            hour: Rgb {
+                r: 255,
+                g: 40,
+                b: 0,
+            },
+            minute: Rgb {
+                r: 0,
+                g: 200,
+                b: 80,
+            },
+            second: Some(Rgb {
+                r: 40,
+                g: 80,
+                b: 255,
+            }),
+            background: Rgb { r: 0, g: 0, b: 0 },
+            top_offset: 0,
+            reversed: false,
+        }
+    }
+}
+
+impl ClockStyle {
+    /// Set a hand color by slot index
+    ///
+    /// Index 0 is the hour hand, 1 the minute hand and 2 the second hand.
+    /// Other indices are ignored.
+    pub const fn set_color_at(&mut self, index: usize, color: Rgb) {
+        match index {
+            0 => self.hour = color,
+            1 => self.minute = color,
+            2 => self.second = Some(color),
+            _ => {}
+        }
+    }
+}
+
+/// Analog clock effect for LED rings
+#[derive(Debug, Clone)]
+pub struct ClockEffect {
+    clock: DayClock,
+    style: ClockStyle,
+    /// Shown colors of the hour, minute and second hands
+    hands: [ValueTransition<Rgb>; 3],
+}
+
+impl Default for ClockEffect {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClockEffect {
+    /// Create a new clock effect
+    ///
+    /// Until a clock is set, `Instant` zero is treated as midnight.
+    pub fn new() -> Self {
+        let style = ClockStyle::default();
+        Self {
+            clock: DayClock::default(),
+            style,
+            hands: Self::hand_colors(&style).map(ValueTransition::new_rgb),
+        }
+    }
+
+    /// Set the style
+    #[must_use]
+    pub fn with_style(mut self, style: ClockStyle) -> Self {
+        self.set_style(style);
+        self
+    }
+
+    /// Replace the style
+    pub fn set_style(&mut self, style: ClockStyle) {
+        self.style = style;
+        let colors = Self::hand_colors(&style);
+        for (hand, color) in self.hands.iter_mut().zip(colors) {
+            hand.set(color, Duration::from_millis(0), Instant::from_millis(0));
+        }
+    }
+
+    /// Set a hand color by slot index with smooth transition
+    ///
+    /// See [`ClockStyle::set_color_at`]. A hidden second hand appears in the
+    /// new color right away.
+    pub fn set_color_at(
+        &mut self,
+        index: usize,
+        color: Rgb,
+        duration: Duration,
+        now: Instant,
+    ) {
+        let duration = if index == 2 && self.style.second.is_none() {
+            Duration::from_millis(0)
+        } else {
+            duration
+        };
+        self.style.set_color_at(index, color);
+        if let Some(hand) = self.hands.get_mut(index) {
+            hand.set(color, duration, now);
+        }
+    }
+
+    /// Set the color space used for color transitions
+    pub fn set_blending(&mut self, blending: ColorBlending) {
+        for hand in &mut self.hands {
+            hand.set_blender(blending.blender());
+        }
+    }
+
+    /// Hand colors of a style, a hidden second hand keeps the background
+    const fn hand_colors(style: &ClockStyle) -> [Rgb; 3] {
+        let second = match style.second {
+            Some(color) => color,
+            None => style.background,
+        };
+        [style.hour, style.minute, second]
+    }
+
+    /// Set the clock used to derive the time of day
+    pub fn set_clock(&mut self, clock: DayClock) {
+        self.clock = clock;
+    }
+
+    /// Add a soft hand at ring `angle` to the frame
+    #[allow(clippy::cast_possible_truncation)]
+    fn draw_hand(&self, leds: &mut [Rgb], angle: u32, width: u32, color: Rgb) {
+        let len = leds.len();
+        let center = ring_position(
+            len,
+            usize::from(self.style.top_offset),
+            angle,
+            self.style.reversed,
+        );
+        for (i, led) in leds.iter_mut().enumerate() {
+            let distance = ring_distance(len, (i as u32) << 8, center);
+            if distance >= width {
+                continue;
+            }
+            // Linear fall-off towards the edges of the hand
+            let level = (255 - distance * 255 / width) as u8;
+            *led = Rgb {
+                r: led.r.saturating_add(scale8(color.r, level)),
+                g: led.g.saturating_add(scale8(color.g, level)),
+                b: led.b.saturating_add(scale8(color.b, level)),
+            };
+        }
+    }
+
+    /// Angle of a hand that completes a turn every `period_ms`
+    #[allow(clippy::cast_possible_truncation)]
+    const fn angle(elapsed_ms: u32, period_ms: u32) -> u32 {
+        ((elapsed_ms % period_ms) as u64 * RING_TURN as u64 / period_ms as u64)
+            as u32
+    }
+}
+
+impl Effect for ClockEffect {
+    fn render(&mut self, now: Instant, leds: &mut [Rgb]) {
+        let [hour_color, minute_color, second_color] =
+            self.hands.each_mut().map(|hand| {
+                hand.tick(now);
+                hand.current()
+            });
+        let time = self.clock.at(now).as_millis();
+        leds.fill(self.style.background);
+
+        let hour = Self::angle(time, MS_PER_HALF_DAY);
+        let minute = Self::angle(time, MS_PER_HOUR);
+        self.draw_hand(leds, hour, HOUR_WIDTH, hour_color);
+        self.draw_hand(leds, minute, MINUTE_WIDTH, minute_color);
+        if self.style.second.is_some() {
+            let second = Self::angle(time, MS_PER_MINUTE);
+            self.draw_hand(leds, second, SECOND_WIDTH, second_color);
+        }
+    }
+
+    fn is_transitioning(&self) -> bool {
+        self.hands.iter().any(ValueTransition::is_transitioning)
+    }
+}
@@ -1,25 +1,24 @@
 //! Flow effect with palette-based presets
 
-use embassy_time::Instant;
+use embassy_time::{Duration, Instant};
 
 use super::Effect;
 use crate::{
-    color::{Rgb, blend_colors, rgb_from_u32},
+    beat::Beat,
+    color::{MAX_PALETTE_STOPS, Palette, Rgb, blend_colors, hex_palette},
     math8::{blend8, ease_in_out_quad, scale8},
+    transition::{ColorBlending, ValueBlender, ValueTransition},
 };
 
-/// Create a palette from a list of hex colors (0xRRGGBB format)
-macro_rules! hex_palette {
-    ($($color:expr),*) => {
-        [
-            $(rgb_from_u32($color)),*
-        ]
-    };
-}
+/// Extra flow speed at the peak of a beat (255 = double speed)
+const BEAT_SURGE: u64 = 3 * 255;
+
+/// Maximum number of colors in a user flow palette
+pub const MAX_FLOW_PALETTE_COLORS: usize = 16;
 
 // Neon palette: cool blue/teal/violet tones
 #[allow(clippy::unreadable_literal)]
-const NEON_PALETTE: [Rgb; 6] = hex_palette![
+const NEON_COLORS: [Rgb; 6] = hex_palette![
     0x002EB8, // Deep blue
     0x00FFD4, // Teal (stronger)
     0x14FF78, // Green (muted/teal-leaning)
@@ -30,7 +29,7 @@ const NEON_PALETTE: [Rgb; 6] = hex_palette![
 
 // Lava lamp palette: warm red/orange/purple tones
 #[allow(clippy::unreadable_literal)]
-const LAVA_LAMP_PALETTE: [Rgb; 5] = hex_palette![
+const LAVA_LAMP_COLORS: [Rgb; 5] = hex_palette![
     0x3C0014, // Dark magenta
     0xD10038, // Deep red
     0xFF5000, // Orange
@@ -40,7 +39,7 @@ const LAVA_LAMP_PALETTE: [Rgb; 5] = hex_palette![
 
 // Sunset palette: night blue → purple/magenta → orange → warm yellow
 #[allow(clippy::unreadable_literal)]
-const SUNSET_PALETTE: [Rgb; 6] = hex_palette![
+const SUNSET_COLORS: [Rgb; 6] = hex_palette![
     0x0B1026, // Night Sky
     0x2B1B54, // Deep Purple
     0x8C2155, // Magenta
@@ -49,6 +48,13 @@ const SUNSET_PALETTE: [Rgb; 6] = hex_palette![
     0xFFD878  // Sun Yellow
 ];
 
+const NEON_PALETTE: Palette =
+    Palette::StaticStops(&Palette::spread_stops(NEON_COLORS));
+const LAVA_LAMP_PALETTE: Palette =
+    Palette::StaticStops(&Palette::spread_stops(LAVA_LAMP_COLORS));
+const SUNSET_PALETTE: Palette =
+    Palette::StaticStops(&Palette::spread_stops(SUNSET_COLORS));
+
 // Balanced tuning: visible motion, still premium
 const LAYER1_PERIOD_MS: u64 = 8_000; // Slow base layer
 const LAYER2_PERIOD_MS: u64 = 5_000; // Faster mid layer
@@ -76,17 +82,86 @@ pub enum FlowVariant {
     Sunset,
 }
 
+/// Palette and layer timing of the flow effect
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlowPalette {
+    palette: Palette,
+    /// Periods of the base, detail and shimmer layers in milliseconds
+    layer_periods: [u64; 3],
+}
+
+impl FlowPalette {
+    /// Create a palette from user colors with the default layer periods
+    ///
+    /// Returns `None` unless there are 1 to 16 colors.
+    pub fn new(colors: &[Rgb]) -> Option<Self> {
+        if colors.len() > MAX_FLOW_PALETTE_COLORS {
+            return None;
+        }
+        Palette::from_colors(colors).map(Self::from_palette)
+    }
+
+    /// Create a flow palette from any palette with the default layer periods
+    pub const fn from_palette(palette: Palette) -> Self {
+        Self {
+            palette,
+            layer_periods: [LAYER1_PERIOD_MS, LAYER2_PERIOD_MS, LAYER3_PERIOD_MS],
+        }
+    }
+
+    /// Get the built-in palette of a flow variant
+    pub const fn from_variant(variant: FlowVariant) -> Self {
+        Self::from_palette(match variant {
+            FlowVariant::Neon => NEON_PALETTE,
+            FlowVariant::LavaLamp => LAVA_LAMP_PALETTE,
+            FlowVariant::Sunset => SUNSET_PALETTE,
+        })
+    }
+
+    /// Set the periods of the base, detail and shimmer noise layers
+    ///
+    /// Longer periods give slower motion. Periods are at least 1 ms.
+    #[must_use]
+    pub fn with_layer_periods(
+        mut self,
+        base: Duration,
+        detail: Duration,
+        shimmer: Duration,
+    ) -> Self {
+        self.layer_periods =
+            [base, detail, shimmer].map(|period| period.as_millis().max(1));
+        self
+    }
+
+    /// Get the palette
+    pub const fn palette(&self) -> &Palette {
+        &self.palette
+    }
+
+    /// Get the periods of the base, detail and shimmer layers
+    pub fn layer_periods(&self) -> [Duration; 3] {
+        self.layer_periods.map(Duration::from_millis)
+    }
+}
+
 /// Flow effect with layered flowing gradients
 ///
 /// This effect uses multi-layer value noise to create smooth organic motion.
-/// Different palettes produce different visual themes.
+/// Different palettes produce different visual themes. A user palette can
+/// replace the built-in one, crossfading from the previous palette.
 #[derive(Debug, Clone)]
-#[allow(clippy::struct_field_names)]
 pub struct FlowEffect {
-    layer1_period: u64,
-    layer2_period: u64,
-    layer3_period: u64,
     variant: FlowVariant,
+    palette: FlowPalette,
+    previous: Option<FlowPalette>,
+    crossfade: ValueTransition<u8>,
+    /// Blender of the palette crossfade
+    blend: ValueBlender<Rgb>,
+    /// Beat to surge the flow on, if beat sync is enabled
+    beat: Option<Beat>,
+    /// Time the flow ran ahead of the clock due to beat surges
+    surge_ms: u64,
+    last_frame: Option<Instant>,
 }
 
 impl Default for FlowEffect {
@@ -99,13 +174,105 @@ impl FlowEffect {
     /// Create a new flow effect with the specified variant
     pub const fn new(variant: FlowVariant) -> Self {
         Self {
-            layer1_period: LAYER1_PERIOD_MS,
-            layer2_period: LAYER2_PERIOD_MS,
-            layer3_period: LAYER3_PERIOD_MS,
             variant,
+            palette: FlowPalette::from_variant(variant),
+            previous: None,
+            crossfade: ValueTransition::new_u8(255),
+            blend: blend_colors,
+            beat: None,
+            surge_ms: 0,
+            last_frame: None,
         }
     }
 
+    /// Set a user palette
+    #[must_use]
+    pub fn with_palette(mut self, palette: FlowPalette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Replace the palette, crossfading over `duration`
+    ///
+    /// `None` restores the built-in palette of the variant. A running
+    /// crossfade continues from the palette shown at `now`.
+    pub fn set_palette(
+        &mut self,
+        palette: Option<FlowPalette>,
+        duration: Duration,
+        now: Instant,
+    ) {
+        let palette =
+            palette.unwrap_or_else(|| FlowPalette::from_variant(self.variant));
+        if palette == self.palette {
+            return;
+        }
+        self.crossfade.tick(now);
+        let previous = match &self.previous {
+            Some(previous) if self.crossfade.is_transitioning() => {
+                self.blended_palette(previous)
+            }
+            _ => self.palette.clone(),
+        };
+        self.palette = palette;
+        self.crossfade = ValueTransition::new_u8(0);
+        self.crossfade.set(255, duration, now);
+        self.previous = self.crossfade.is_transitioning().then_some(previous);
+    }
+
+    /// Set the color space used for palette crossfades
+    pub fn set_blending(&mut self, blending: ColorBlending) {
+        self.blend = blending.blender();
+    }
+
+    /// Follow the beat with speed surges, `None` disables it
+    pub fn set_beat(&mut self, beat: Option<Beat>) {
+        self.beat = beat;
+    }
+
+    /// Get the active palette
+    pub const fn palette(&self) -> &FlowPalette {
+        &self.palette
+    }
+
+    /// Check if the effect is crossfading between palettes
+    ///
+    /// Palette crossfades don't hold back color changes, so they are not
+    /// reported by [`Effect::is_transitioning`].
+    pub fn is_crossfading(&self) -> bool {
+        self.crossfade.is_transitioning()
+    }
+
+    /// Mix `previous` into the active palette at the current crossfade
+    ///
+    /// The layer timing of the dominant palette is kept.
+    #[allow(clippy::cast_possible_truncation)]
+    fn blended_palette(&self, previous: &FlowPalette) -> FlowPalette {
+        let amount = self.crossfade.current();
+        let mut colors = [Rgb::default(); MAX_PALETTE_STOPS];
+        let last = colors.len() - 1;
+        for (i, color) in colors.iter_mut().enumerate() {
+            let index = (i * 255 / last) as u8;
+            *color = (self.blend)(
+                previous.palette.sample(index),
+                self.palette.palette.sample(index),
+                amount,
+            );
+        }
+        let layer_periods = if amount < 128 {
+            previous.layer_periods
+        } else {
+            self.palette.layer_periods
+        };
+        Palette::from_colors(&colors).map_or_else(
+            || self.palette.clone(),
+            |palette| FlowPalette {
+                palette,
+                layer_periods,
+            },
+        )
+    }
+
     /// Simple deterministic hash for noise generation
     #[inline]
     const fn hash(x: u64) -> u32 {
@@ -145,34 +312,9 @@ impl FlowEffect {
         blend8(v0, v1, t)
     }
 
-    /// Sample the palette at position t (0-255)
-    #[allow(clippy::cast_possible_truncation)]
-    fn sample_palette(palette: &[Rgb], t: u8) -> Rgb {
-        // Map t (0-255) across N colors (N-1 segments) with blending.
-        let segments = palette.len().saturating_sub(1);
-        if segments == 0 {
-            return palette.first().copied().unwrap_or(Rgb { r: 0, g: 0, b: 0 });
-        }
-
-        let scaled = u16::from(t) * (segments as u16); // 0..255*(N-1)
-        let segment = (scaled >> 8).min(segments.saturating_sub(1) as u16) as usize;
-        let local_t = (scaled & 0xFF) as u8;
-
-        blend_colors(palette[segment], palette[segment + 1], local_t)
-    }
-
-    /// Get the palette for the current variant
-    fn palette(&self) -> &'static [Rgb] {
-        match self.variant {
-            FlowVariant::Neon => &NEON_PALETTE,
-            FlowVariant::LavaLamp => &LAVA_LAMP_PALETTE,
-            FlowVariant::Sunset => &SUNSET_PALETTE,
-        }
-    }
-
     /// Combine multiple noise layers into a final value
     #[allow(clippy::cast_possible_truncation)]
-    fn combined_noise(&self, i: u32, len: u32, now: Instant) -> u8 {
+    fn combined_noise(periods: &[u64; 3], i: u32, len: u32, now: Instant) -> u8 {
         let time_ms = now.as_millis();
 
         // Derive cell sizes from strip length so the effect stays smooth.
@@ -190,9 +332,9 @@ impl FlowEffect {
 
         // High-resolution phase (16.16): continuous motion with no stepping.
         // We intentionally do NOT modulo time to avoid visible jumps on wrap.
-        let p1 = (time_ms << 16) / self.layer1_period;
-        let p2 = (time_ms << 16) / self.layer2_period;
-        let p3 = (time_ms << 16) / self.layer3_period;
+        let p1 = (time_ms << 16) / periods[0];
+        let p2 = (time_ms << 16) / periods[1];
+        let p3 = (time_ms << 16) / periods[2];
 
         // Layer directions differ slightly for depth/parallax.
         let n1 = Self::value_noise(x1.wrapping_add(p1));
@@ -204,31 +346,58 @@ impl FlowEffect {
             (u16::from(n1) * 128 + u16::from(n2) * 77 + u16::from(n3) * 51) >> 8;
         combined as u8
     }
+
+    /// Color of LED `i` for the given palette
+    fn shade(palette: &FlowPalette, i: u32, len: u32, now: Instant) -> Rgb {
+        // Get combined noise value
+        let noise = Self::combined_noise(&palette.layer_periods, i, len, now);
+
+        // Sample palette and apply subtle brightness modulation
+        let base_color = palette.palette.sample(noise);
+
+        // Add subtle brightness variation based on noise for "silky" feel
+        let brightness_mod = scale8(noise, 64).saturating_add(191); // 75%-100% range
+        Rgb {
+            r: scale8(base_color.r, brightness_mod),
+            g: scale8(base_color.g, brightness_mod),
+            b: scale8(base_color.b, brightness_mod),
+        }
+    }
 }
 
 impl Effect for FlowEffect {
     fn render(&mut self, now: Instant, leds: &mut [Rgb]) {
+        self.crossfade.tick(now);
+        if !self.crossfade.is_transitioning() {
+            self.previous = None;
+        }
         if leds.is_empty() {
             return;
         }
 
+        // Beats push the flow ahead of the clock
+        let elapsed_ms = self
+            .last_frame
+            .map_or(0, |last| now.saturating_duration_since(last).as_millis());
+        self.last_frame = Some(now);
+        if let Some(beat) = self.beat {
+            self.surge_ms +=
+                elapsed_ms * u64::from(beat.pulse) * BEAT_SURGE / (255 * 255);
+        }
+        let now = Instant::from_millis(now.as_millis() + self.surge_ms);
+
         let len = u32::try_from(leds.len()).unwrap_or(u32::MAX);
-        let palette = self.palette();
+        let crossfade = self.crossfade.current();
 
         for (i, led) in leds.iter_mut().enumerate() {
-            // Get combined noise value
             let i_u32 = u32::try_from(i).unwrap_or(u32::MAX);
-            let noise = self.combined_noise(i_u32, len, now);
-
-            // Sample palette and apply subtle brightness modulation
-            let base_color = Self::sample_palette(palette, noise);
-
-            // Add subtle brightness variation based on noise for "silky" feel
-            let brightness_mod = scale8(noise, 64).saturating_add(191); // 75%-100% range
-            *led = Rgb {
-                r: scale8(base_color.r, brightness_mod),
-                g: scale8(base_color.g, brightness_mod),
-                b: scale8(base_color.b, brightness_mod),
+            let color = Self::shade(&self.palette, i_u32, len, now);
+            *led = match &self.previous {
+                Some(previous) => {
+                    let from = Self::shade(previous, i_u32, len, now);
+                    (self.blend)(from, color, crossfade)
+                }
+                None => color,
             };
         }
     }
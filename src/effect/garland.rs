@@ -0,0 +1,298 @@
+//! Holiday garland effect
+//!
+//! Lights the strip in groups of alternating user colors, like the bulbs of a
+//! garland. The colors slowly crossfade in place or chase along the strip,
+//! optionally with random twinkles.
+
+use embassy_time::{Duration, Instant};
+use heapless::Vec;
+
+use super::Effect;
+use crate::{
+    color::{Rgb, blend_colors},
+    math8::{ease_in_out_quad, scale8},
+    transition::{ColorBlending, ValueBlender, ValueTransition},
+};
+
+/// Minimum number of garland colors
+pub const MIN_GARLAND_COLORS: usize = 2;
+/// Maximum number of garland colors
+pub const MAX_GARLAND_COLORS: usize = 4;
+
+/// Default time for one color step
+const DEFAULT_STEP: Duration = Duration::from_millis(3_000);
+/// Length of a twinkle
+const TWINKLE_MS: u64 = 240;
+/// Chance of a group twinkling during a twinkle slot (out of 256)
+const TWINKLE_CHANCE: u32 = 12;
+/// Maximum amount of white mixed into a twinkling group
+const TWINKLE_HIGHLIGHT: u8 = 160;
+
+/// Second color used until the user picks garland colors
+const WARM_WHITE: Rgb = Rgb {
+    r: 255,
+    g: 170,
+    b: 80,
+};
+
+/// Colors of the garland bulbs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GarlandColors {
+    colors: Vec<Rgb, MAX_GARLAND_COLORS>,
+}
+
+impl GarlandColors {
+    /// Create a garland color set
+    ///
+    /// Returns `None` unless there are 2 to 4 colors.
+    pub fn new(colors: &[Rgb]) -> Option<Self> {
+        if colors.len() < MIN_GARLAND_COLORS {
+            return None;
+        }
+        Some(Self {
+            colors: Vec::from_slice(colors).ok()?,
+        })
+    }
+
+    /// Get the colors
+    pub fn colors(&self) -> &[Rgb] {
+        &self.colors
+    }
+
+    /// Replace the color at `index`
+    ///
+    /// An index just past the last color appends a color, other indices out
+    /// of range are ignored.
+    pub fn set_color_at(&mut self, index: usize, color: Rgb) {
+        let len = self.colors.len();
+        match self.colors.get_mut(index) {
+            Some(current) => *current = color,
+            None if index == len => {
+                let _ = self.colors.push(color);
+            }
+            None => {}
+        }
+    }
+}
+
+/// Animation of the garland colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GarlandMode {
+    /// Groups stay in place and crossfade to the next color every step
+    Fade,
+    /// Groups move along the strip by one group every step
+    Chase,
+}
+
+/// Layout and animation of the garland
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GarlandStyle {
+    /// Color animation
+    pub mode: GarlandMode,
+    /// Number of lit LEDs per group
+    pub group_size: u8,
+    /// Number of dark LEDs between groups
+    pub gap: u8,
+    /// Time for one color step
+    pub step: Duration,
+    /// Randomly flash groups towards white
+    pub twinkle: bool,
+}
+
+impl Default for GarlandStyle {
+    fn default() -> Self {
+        Self {
+            mode: GarlandMode::Fade,
+            group_size: 3,
+            gap: 1,
+            step: DEFAULT_STEP,
+            twinkle: false,
+        }
+    }
+}
+
+/// Garland effect with alternating groups of user colors
+#[derive(Debug, Clone)]
+pub struct GarlandEffect {
+    colors: Vec<ValueTransition<Rgb>, MAX_GARLAND_COLORS>,
+    /// Blender of the color transitions
+    blend: ValueBlender<Rgb>,
+    style: GarlandStyle,
+}
+
+impl GarlandEffect {
+    /// Create a garland alternating `color` and warm white
+    pub fn new(color: Rgb) -> Self {
+        let mut colors = Vec::new();
+        let _ = colors.push(ValueTransition::new_rgb(color));
+        let _ = colors.push(ValueTransition::new_rgb(WARM_WHITE));
+        Self {
+            colors,
+            blend: blend_colors,
+            style: GarlandStyle::default(),
+        }
+    }
+
+    /// Set the garland colors
+    #[must_use]
+    pub fn with_colors(mut self, colors: &GarlandColors) -> Self {
+        self.colors = colors
+            .colors()
+            .iter()
+            .map(|color| ValueTransition::new(*color, self.blend))
+            .collect();
+        self
+    }
+
+    /// Set the style
+    #[must_use]
+    pub fn with_style(mut self, style: GarlandStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Replace the style
+    pub fn set_style(&mut self, style: GarlandStyle) {
+        self.style = style;
+    }
+
+    /// Set the first color with smooth transition
+    pub fn set_color(&mut self, color: Rgb, duration: Duration, now: Instant) {
+        self.set_color_at(0, color, duration, now);
+    }
+
+    /// Set the color at `index` with smooth transition
+    ///
+    /// An index just past the last color appends a color, other indices out
+    /// of range are ignored.
+    pub fn set_color_at(
+        &mut self,
+        index: usize,
+        color: Rgb,
+        duration: Duration,
+        now: Instant,
+    ) {
+        let len = self.colors.len();
+        match self.colors.get_mut(index) {
+            Some(current) => current.set(color, duration, now),
+            None if index == len => {
+                let _ = self.colors.push(ValueTransition::new(color, self.blend));
+            }
+            None => {}
+        }
+    }
+
+    /// Set the color space used for color transitions
+    pub fn set_blending(&mut self, blending: ColorBlending) {
+        self.blend = blending.blender();
+        for color in &mut self.colors {
+            color.set_blender(self.blend);
+        }
+    }
+
+    /// Set all colors with smooth transition
+    ///
+    /// Colors added to the set appear immediately.
+    pub fn set_colors(
+        &mut self,
+        colors: &GarlandColors,
+        duration: Duration,
+        now: Instant,
+    ) {
+        self.colors.truncate(colors.colors().len());
+        for (i, color) in colors.colors().iter().enumerate() {
+            match self.colors.get_mut(i) {
+                Some(current) => current.set(*color, duration, now),
+                None => {
+                    let _ =
+                        self.colors.push(ValueTransition::new(*color, self.blend));
+                }
+            }
+        }
+    }
+
+    /// Pseudo-random hash of a group and a twinkle slot
+    const fn hash(group: u32, slot: u32) -> u32 {
+        let mut x = group.wrapping_mul(0x9e37_79b9) ^ slot.wrapping_mul(0x85eb_ca6b);
+        x ^= x >> 16;
+        x = x.wrapping_mul(0x7feb_352d);
+        x ^= x >> 15;
+        x
+    }
+
+    /// Twinkle highlight of a group at the given time
+    #[allow(clippy::cast_possible_truncation)]
+    const fn twinkle(group: u32, time_ms: u64) -> u8 {
+        let slot = (time_ms / TWINKLE_MS) as u32;
+        if Self::hash(group, slot) % 256 >= TWINKLE_CHANCE {
+            return 0;
+        }
+        // Triangle envelope over the slot
+        let phase = (time_ms % TWINKLE_MS * 510 / TWINKLE_MS) as u32;
+        let level = if phase > 255 { 510 - phase } else { phase };
+        scale8(level as u8, TWINKLE_HIGHLIGHT)
+    }
+}
+
+impl Effect for GarlandEffect {
+    const PRECISE_COLORS: bool = true;
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn render(&mut self, now: Instant, leds: &mut [Rgb]) {
+        let mut colors = [Rgb { r: 0, g: 0, b: 0 }; MAX_GARLAND_COLORS];
+        for (color, transition) in colors.iter_mut().zip(self.colors.iter_mut()) {
+            transition.tick(now);
+            *color = transition.current();
+        }
+        let count = self.colors.len().max(1);
+
+        let time_ms = now.as_millis();
+        let step_ms = self.style.step.as_millis().max(1);
+        let steps = (time_ms / step_ms) as usize;
+        let group_size = usize::from(self.style.group_size.max(1));
+        let cell = group_size + usize::from(self.style.gap);
+        let period = cell * count;
+
+        // Chasing moves the pattern one group per step, LED by LED
+        let offset = match self.style.mode {
+            GarlandMode::Fade => 0,
+            GarlandMode::Chase => {
+                (time_ms * cell as u64 / step_ms) as usize % period
+            }
+        };
+        let fade = ((time_ms % step_ms) * 255 / step_ms) as u8;
+        let white = Rgb {
+            r: 255,
+            g: 255,
+            b: 255,
+        };
+
+        for (i, led) in leds.iter_mut().enumerate() {
+            let position = (i + period - offset) % period;
+            if position % cell >= group_size {
+                *led = Rgb { r: 0, g: 0, b: 0 };
+                continue;
+            }
+
+            let group = position / cell;
+            *led = match self.style.mode {
+                GarlandMode::Fade => {
+                    let index = (group + steps) % count;
+                    let next = (index + 1) % count;
+                    blend_colors(colors[index], colors[next], ease_in_out_quad(fade))
+                }
+                GarlandMode::Chase => colors[group],
+            };
+
+            if self.style.twinkle {
+                let strip_group = (i / cell) as u32;
+                let highlight = Self::twinkle(strip_group, time_ms);
+                *led = blend_colors(*led, white, highlight);
+            }
+        }
+    }
+
+    fn is_transitioning(&self) -> bool {
+        self.colors.iter().any(ValueTransition::is_transitioning)
+    }
+}
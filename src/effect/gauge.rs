@@ -0,0 +1,259 @@
+//! Progress bar / gauge effect
+//!
+//! Fills the strip proportionally to a level value, e.g. print progress or
+//! battery charge. The level is set at runtime and animates smoothly.
+
+use embassy_time::{Duration, Instant};
+
+use super::Effect;
+use crate::{
+    color::{Rgb, blend_colors},
+    math8::{scale8, sin8},
+    transition::{ColorBlending, ValueTransition},
+};
+
+/// Full scale gauge level (100%)
+pub const GAUGE_FULL: u16 = u16::MAX;
+
+/// Default duration of level animations
+const DEFAULT_LEVEL_TRANSITION: Duration = Duration::from_millis(600);
+
+/// Period of the leading edge shimmer
+const EDGE_PERIOD_MS: u64 = 1_200;
+
+/// Maximum amount of white mixed into the leading edge
+const EDGE_HIGHLIGHT: u8 = 96;
+
+/// Convert a percentage (0-100) to a gauge level (0-65535)
+#[allow(clippy::cast_possible_truncation)]
+pub const fn percent_to_level(percent: u8) -> u16 {
+    let percent = if percent > 100 { 100 } else { percent };
+    (percent as u32 * GAUGE_FULL as u32 / 100) as u16
+}
+
+/// Visual style of the gauge
+///
+/// In the renderer, secondary and tertiary colors set after the style replace
+/// the empty color and the gradient start, see [`Self::set_color_at`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GaugeStyle {
+    /// Color of the unfilled part
+    pub empty: Rgb,
+    /// Start color of an optional gradient
+    ///
+    /// When set, the filled part fades from this color at the start of the
+    /// strip to the fill color at its end.
+    pub gradient_from: Option<Rgb>,
+    /// Animate the leading edge of the fill
+    pub animated_edge: bool,
+}
+
+impl GaugeStyle {
+    /// Set a color by slot index
+    ///
+    /// Index 1 is the empty color and 2 the gradient start. Other indices are
+    /// ignored, the fill color is not part of the style.
+    pub const fn set_color_at(&mut self, index: usize, color: Rgb) {
+        match index {
+            1 => self.empty = color,
+            2 => self.gradient_from = Some(color),
+            _ => {}
+        }
+    }
+}
+
+impl Default for GaugeStyle {
+    fn default() -> Self {
+        Self {
+            empty: Rgb { r: 0, g: 0, b: 0 },
+            gradient_from: None,
+            animated_edge: true,
+        }
+    }
+}
+
+/// Gauge effect
+///
+/// Fills LEDs with the user color up to the current level. The LED at the
+/// fill boundary is partially lit for sub-LED precision.
+#[derive(Debug, Clone)]
+pub struct GaugeEffect {
+    color: ValueTransition<Rgb>,
+    /// Shown color of the unfilled part
+    empty: ValueTransition<Rgb>,
+    /// Shown start color of the gradient, if the style has one
+    gradient_from: ValueTransition<Rgb>,
+    level: ValueTransition<u16>,
+    level_transition: Duration,
+    style: GaugeStyle,
+}
+
+impl GaugeEffect {
+    /// Create a new empty gauge with the given fill color
+    pub fn new(color: Rgb) -> Self {
+        let style = GaugeStyle::default();
+        Self {
+            color: ValueTransition::new_rgb(color),
+            empty: ValueTransition::new_rgb(style.empty),
+            gradient_from: ValueTransition::new_rgb(color),
+            level: ValueTransition::new_u16(0),
+            level_transition: DEFAULT_LEVEL_TRANSITION,
+            style,
+        }
+    }
+
+    /// Set the visual style
+    #[must_use]
+    pub fn with_style(mut self, style: GaugeStyle) -> Self {
+        self.set_style(style);
+        self
+    }
+
+    /// Set the initial level
+    #[must_use]
+    pub fn with_level(mut self, level: u16) -> Self {
+        self.level = ValueTransition::new_u16(level);
+        self
+    }
+
+    /// Set the duration of level animations
+    #[must_use]
+    pub fn with_level_transition(mut self, duration: Duration) -> Self {
+        self.level_transition = duration;
+        self
+    }
+
+    /// Replace the visual style
+    pub fn set_style(&mut self, style: GaugeStyle) {
+        self.style = style;
+        let start = Instant::from_millis(0);
+        self.empty.set(style.empty, Duration::from_millis(0), start);
+        if let Some(from) = style.gradient_from {
+            self.gradient_from
+                .set(from, Duration::from_millis(0), start);
+        }
+    }
+
+    /// Set the fill color with smooth transition
+    pub fn set_color(&mut self, color: Rgb, duration: Duration, now: Instant) {
+        self.color.set(color, duration, now);
+    }
+
+    /// Set a color by slot index with smooth transition
+    ///
+    /// Index 0 is the fill color, 1 the empty color and 2 the gradient start.
+    /// Other indices are ignored. A new gradient fades in from the fill
+    /// color.
+    pub fn set_color_at(
+        &mut self,
+        index: usize,
+        color: Rgb,
+        duration: Duration,
+        now: Instant,
+    ) {
+        match index {
+            0 => self.set_color(color, duration, now),
+            1 => self.empty.set(color, duration, now),
+            2 => {
+                if self.style.gradient_from.is_none() {
+                    let fill = self.color.current();
+                    self.gradient_from.set(fill, Duration::from_millis(0), now);
+                }
+                self.gradient_from.set(color, duration, now);
+            }
+            _ => {}
+        }
+        self.style.set_color_at(index, color);
+    }
+
+    /// Set the color space used for color transitions
+    pub fn set_blending(&mut self, blending: ColorBlending) {
+        let blend = blending.blender();
+        self.color.set_blender(blend);
+        self.empty.set_blender(blend);
+        self.gradient_from.set_blender(blend);
+    }
+
+    /// Animate to a new level (0-65535)
+    pub fn set_level(&mut self, level: u16, now: Instant) {
+        self.level.set(level, self.level_transition, now);
+    }
+
+    /// Get the currently displayed level
+    pub const fn level(&self) -> u16 {
+        self.level.current()
+    }
+
+    /// Color of a filled LED at index `i`
+    #[allow(clippy::cast_possible_truncation)]
+    fn fill_color(&self, color: Rgb, i: usize, len: usize) -> Rgb {
+        if self.style.gradient_from.is_none() {
+            return color;
+        }
+        let from = self.gradient_from.current();
+        let last = len.saturating_sub(1).max(1);
+        let amount = (i.min(last) * 255 / last) as u8;
+        blend_colors(from, color, amount)
+    }
+}
+
+impl Effect for GaugeEffect {
+    const PRECISE_COLORS: bool = true;
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn render(&mut self, now: Instant, leds: &mut [Rgb]) {
+        self.color.tick(now);
+        self.empty.tick(now);
+        self.gradient_from.tick(now);
+        self.level.tick(now);
+
+        let len = leds.len();
+        let color = self.color.current();
+        let empty = self.empty.current();
+
+        // Fill position in 8.8 fixed-point LEDs
+        let fill = u64::from(self.level.current()) * len as u64 * 256
+            / u64::from(GAUGE_FULL);
+        let full = (fill >> 8) as usize;
+        let partial = (fill & 0xFF) as u8;
+
+        let shimmer = if self.style.animated_edge {
+            let phase =
+                ((now.as_millis() % EDGE_PERIOD_MS) * 256 / EDGE_PERIOD_MS) as u8;
+            scale8(sin8(phase), EDGE_HIGHLIGHT)
+        } else {
+            0
+        };
+        let white = Rgb {
+            r: 255,
+            g: 255,
+            b: 255,
+        };
+
+        for (i, led) in leds.iter_mut().enumerate() {
+            *led = if i < full {
+                self.fill_color(color, i, len)
+            } else if i == full && partial > 0 {
+                blend_colors(empty, self.fill_color(color, i, len), partial)
+            } else {
+                empty
+            };
+        }
+
+        // Highlight the leading LED, weighted by how much of it is lit
+        let (edge, edge_fill) = if partial > 0 {
+            (Some(full), partial)
+        } else {
+            (full.checked_sub(1), 255)
+        };
+        if let Some(led) = edge.and_then(|edge| leds.get_mut(edge)) {
+            *led = blend_colors(*led, white, scale8(shimmer, edge_fill));
+        }
+    }
+
+    fn is_transitioning(&self) -> bool {
+        self.color.is_transitioning()
+            || self.empty.is_transitioning()
+            || self.gradient_from.is_transitioning()
+    }
+}
@@ -0,0 +1,218 @@
+//! User-defined gradient effect
+//!
+//! Renders a list of color stops across the rendering bounds, optionally
+//! scrolling and mirrored around the center.
+
+use embassy_time::{Duration, Instant};
+use heapless::Vec;
+
+use super::Effect;
+use crate::{
+    bounds::center_of,
+    color::{COLOR_SLOTS, ColorStop, Rgb, blend_colors, mirror_half, sample_stops},
+    transition::{ColorBlending, ValueBlender, ValueTransition},
+};
+
+/// Minimum number of color stops in a gradient
+pub const MIN_GRADIENT_STOPS: usize = 2;
+/// Maximum number of color stops in a gradient
+pub const MAX_GRADIENT_STOPS: usize = 8;
+
+/// Stops, scrolling and mirroring of a user gradient
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GradientConfig {
+    stops: Vec<ColorStop, MAX_GRADIENT_STOPS>,
+    scroll_period: Option<Duration>,
+    mirror: bool,
+}
+
+impl GradientConfig {
+    /// Create a gradient with evenly spaced colors
+    ///
+    /// Returns `None` unless there are 2 to 8 colors.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn from_colors(colors: &[Rgb]) -> Option<Self> {
+        if !(MIN_GRADIENT_STOPS..=MAX_GRADIENT_STOPS).contains(&colors.len()) {
+            return None;
+        }
+        let last = colors.len() - 1;
+        let stops = colors
+            .iter()
+            .enumerate()
+            .map(|(i, color)| ColorStop::new((i * 255 / last) as u8, *color))
+            .collect();
+        Some(Self::with_stops(stops))
+    }
+
+    /// Create a gradient from positioned color stops
+    ///
+    /// Stops may be given in any order. Returns `None` unless there are 2
+    /// to 8 stops.
+    pub fn from_stops(stops: &[ColorStop]) -> Option<Self> {
+        if !(MIN_GRADIENT_STOPS..=MAX_GRADIENT_STOPS).contains(&stops.len()) {
+            return None;
+        }
+        let mut stops: Vec<ColorStop, MAX_GRADIENT_STOPS> =
+            stops.iter().copied().collect();
+        stops.sort_unstable_by_key(|stop| stop.position);
+        Some(Self::with_stops(stops))
+    }
+
+    const fn with_stops(stops: Vec<ColorStop, MAX_GRADIENT_STOPS>) -> Self {
+        Self {
+            stops,
+            scroll_period: None,
+            mirror: false,
+        }
+    }
+
+    /// Scroll the gradient, moving one full length every `period`
+    #[must_use]
+    pub fn with_scroll(mut self, period: Duration) -> Self {
+        self.scroll_period = (period.as_millis() > 0).then_some(period);
+        self
+    }
+
+    /// Mirror the gradient around the center of the bounds
+    #[must_use]
+    pub fn with_mirror(mut self) -> Self {
+        self.mirror = true;
+        self
+    }
+
+    /// Get the color stops, sorted by position
+    pub fn stops(&self) -> &[ColorStop] {
+        &self.stops
+    }
+}
+
+/// Gradient effect rendering user-supplied color stops
+///
+/// Without a configuration the gradient spreads the slot colors evenly,
+/// fading to black when only the first one is set.
+#[derive(Debug, Clone)]
+pub struct GradientEffect {
+    config: GradientConfig,
+    colors: [Option<ValueTransition<Rgb>>; COLOR_SLOTS],
+    /// Blender of the slot color transitions
+    blend: ValueBlender<Rgb>,
+    configured: bool,
+}
+
+impl GradientEffect {
+    /// Create a gradient effect fading from `color` to black
+    pub fn new(color: Rgb) -> Self {
+        let mut effect = Self {
+            config: GradientConfig::with_stops(Vec::new()),
+            colors: [Some(ValueTransition::new_rgb(color)), None, None],
+            blend: blend_colors,
+            configured: false,
+        };
+        effect.update_from_colors();
+        effect
+    }
+
+    /// Set the gradient configuration
+    #[must_use]
+    pub fn with_config(mut self, config: GradientConfig) -> Self {
+        self.set_config(config);
+        self
+    }
+
+    /// Replace the gradient configuration
+    ///
+    /// Slot colors no longer affect the gradient afterwards.
+    pub fn set_config(&mut self, config: GradientConfig) {
+        self.config = config;
+        self.configured = true;
+    }
+
+    /// Set a slot color of the unconfigured gradient with smooth transition
+    ///
+    /// A slot color that was not set before appears right away. Indices
+    /// beyond the color slots are ignored.
+    pub fn set_color_at(
+        &mut self,
+        index: usize,
+        color: Rgb,
+        duration: Duration,
+        now: Instant,
+    ) {
+        let Some(slot) = self.colors.get_mut(index) else {
+            return;
+        };
+        match slot {
+            Some(current) => current.set(color, duration, now),
+            None => *slot = Some(ValueTransition::new(color, self.blend)),
+        }
+        self.update_from_colors();
+    }
+
+    /// Set the color space used for color transitions
+    pub fn set_blending(&mut self, blending: ColorBlending) {
+        self.blend = blending.blender();
+        for color in self.colors.iter_mut().flatten() {
+            color.set_blender(self.blend);
+        }
+    }
+
+    /// Rebuild the stops from the slot colors
+    fn update_from_colors(&mut self) {
+        if self.configured {
+            return;
+        }
+        let mut colors: Vec<Rgb, { COLOR_SLOTS + 1 }> = self
+            .colors
+            .iter()
+            .flatten()
+            .map(ValueTransition::current)
+            .collect();
+        if colors.len() < MIN_GRADIENT_STOPS {
+            let _ = colors.push(Rgb { r: 0, g: 0, b: 0 });
+        }
+        if let Some(config) = GradientConfig::from_colors(&colors) {
+            self.config = config;
+        }
+    }
+}
+
+impl Effect for GradientEffect {
+    const PRECISE_COLORS: bool = true;
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn render(&mut self, now: Instant, leds: &mut [Rgb]) {
+        if self.is_transitioning() {
+            for color in self.colors.iter_mut().flatten() {
+                color.tick(now);
+            }
+            self.update_from_colors();
+        }
+        let offset = self.config.scroll_period.map_or(0, |period| {
+            let period = period.as_millis();
+            ((now.as_millis() % period) * 256 / period) as u8
+        });
+        let cyclic = self.config.scroll_period.is_some();
+
+        let span = if self.config.mirror {
+            center_of(leds)
+        } else {
+            leds.len()
+        };
+        let last = span.saturating_sub(1).max(1);
+        for (i, led) in leds.iter_mut().take(span).enumerate() {
+            let t = (i * 255 / last) as u8;
+            *led = sample_stops(&self.config.stops, t.wrapping_sub(offset), cyclic);
+        }
+
+        if self.config.mirror {
+            mirror_half(leds);
+        }
+    }
+
+    fn is_transitioning(&self) -> bool {
+        self.colors
+            .iter()
+            .flatten()
+            .any(ValueTransition::is_transitioning)
+    }
+}
@@ -0,0 +1,148 @@
+//! Heartbeat effect
+//!
+//! Pulses the user color in a "lub-dub" double beat. The beat can optionally
+//! ripple out from the center of the rendering bounds.
+
+use embassy_time::{Duration, Instant};
+
+use super::Effect;
+use crate::{
+    bounds::center_of,
+    color::{Rgb, mirror_half},
+    math8::scale8,
+    transition::ValueTransition,
+};
+
+/// Default heart rate in beats per minute
+pub const DEFAULT_BPM: u16 = 60;
+/// Lowest supported heart rate
+pub const MIN_BPM: u16 = 20;
+/// Highest supported heart rate
+pub const MAX_BPM: u16 = 240;
+
+/// Rise time of a single pulse
+const ATTACK_MS: u64 = 40;
+/// Fall time of a single pulse
+const DECAY_MS: u64 = 220;
+/// Delay between "lub" and "dub" relative to the beat period (0-255)
+const DUB_OFFSET: u8 = 72;
+/// Peak brightness of the "dub" pulse
+const DUB_PEAK: u8 = 170;
+/// Ripple propagation delay per LED
+const RIPPLE_MS_PER_LED: u64 = 12;
+
+/// Heartbeat effect with a double pulse in the user color
+#[derive(Debug, Clone)]
+pub struct HeartbeatEffect {
+    color: ValueTransition<Rgb>,
+    bpm: u16,
+    ripple: bool,
+}
+
+impl HeartbeatEffect {
+    /// Create a new heartbeat effect
+    pub fn new(color: Rgb) -> Self {
+        Self {
+            color: ValueTransition::new_rgb(color),
+            bpm: DEFAULT_BPM,
+            ripple: false,
+        }
+    }
+
+    /// Set the heart rate
+    #[must_use]
+    pub fn with_bpm(mut self, bpm: u16) -> Self {
+        self.set_bpm(bpm);
+        self
+    }
+
+    /// Make the beat ripple out from the center
+    #[must_use]
+    pub fn with_ripple(mut self) -> Self {
+        self.ripple = true;
+        self
+    }
+
+    /// Set the heart rate, clamped to `MIN_BPM..=MAX_BPM`
+    pub fn set_bpm(&mut self, bpm: u16) {
+        self.bpm = bpm.clamp(MIN_BPM, MAX_BPM);
+    }
+
+    /// Get the current heart rate
+    pub const fn bpm(&self) -> u16 {
+        self.bpm
+    }
+
+    /// Set the color with smooth transition
+    pub fn set_color(&mut self, color: Rgb, duration: Duration, now: Instant) {
+        self.color.set(color, duration, now);
+    }
+
+    /// Envelope of a single pulse `elapsed_ms` after it started
+    #[allow(clippy::cast_possible_truncation)]
+    const fn pulse(elapsed_ms: u64, peak: u8) -> u8 {
+        let level = if elapsed_ms < ATTACK_MS {
+            (elapsed_ms * 255 / ATTACK_MS) as u8
+        } else if elapsed_ms < ATTACK_MS + DECAY_MS {
+            // Quadratic fall-off reads as a natural decay
+            let remaining = 255 - ((elapsed_ms - ATTACK_MS) * 255 / DECAY_MS) as u8;
+            scale8(remaining, remaining)
+        } else {
+            0
+        };
+        scale8(level, peak)
+    }
+
+    /// Intensity of the double beat at the given time, delayed by `delay_ms`
+    fn intensity(&self, time_ms: u64, delay_ms: u64) -> u8 {
+        let period = 60_000 / u64::from(self.bpm);
+        let phase = (time_ms % period + period - delay_ms % period) % period;
+        let dub_start = period * u64::from(DUB_OFFSET) / 255;
+
+        let lub = Self::pulse(phase, 255);
+        let dub = if phase >= dub_start {
+            Self::pulse(phase - dub_start, DUB_PEAK)
+        } else {
+            0
+        };
+        lub.max(dub)
+    }
+}
+
+impl Effect for HeartbeatEffect {
+    const PRECISE_COLORS: bool = true;
+
+    fn render(&mut self, now: Instant, leds: &mut [Rgb]) {
+        self.color.tick(now);
+        let color = self.color.current();
+        let time_ms = now.as_millis();
+
+        if !self.ripple {
+            let level = self.intensity(time_ms, 0);
+            let pixel = Rgb {
+                r: scale8(color.r, level),
+                g: scale8(color.g, level),
+                b: scale8(color.b, level),
+            };
+            leds.fill(pixel);
+            return;
+        }
+
+        // Ripple out over the first half, then mirror it onto the second
+        let center = center_of(leds);
+        for (i, led) in leds.iter_mut().take(center).enumerate() {
+            let delay = (center - 1 - i) as u64 * RIPPLE_MS_PER_LED;
+            let level = self.intensity(time_ms, delay);
+            *led = Rgb {
+                r: scale8(color.r, level),
+                g: scale8(color.g, level),
+                b: scale8(color.b, level),
+            };
+        }
+        mirror_half(leds);
+    }
+
+    fn is_transitioning(&self) -> bool {
+        self.color.is_transitioning()
+    }
+}
@@ -3,16 +3,67 @@
 //! All effects are stored in an enum to avoid heap allocations.
 //! Each effect implements the `Effect` trait.
 
+mod circadian;
+mod clock;
 mod flow;
+mod garland;
+mod gauge;
+mod gradient;
+mod heartbeat;
+mod plasma;
+mod rain;
 mod rainbow;
+mod spectrum;
 mod static_color;
+mod strobe;
+mod vu_meter;
 
+pub use circadian::{
+    CircadianCurve,
+    CircadianEffect,
+    CircadianPoint,
+    MAX_CIRCADIAN_POINTS,
+};
+pub use clock::{ClockEffect, ClockStyle};
 use embassy_time::{Duration, Instant};
-pub use flow::{FlowEffect, FlowVariant};
+pub use flow::{FlowEffect, FlowPalette, FlowVariant, MAX_FLOW_PALETTE_COLORS};
+pub use garland::{
+    GarlandColors,
+    GarlandEffect,
+    GarlandMode,
+    GarlandStyle,
+    MAX_GARLAND_COLORS,
+    MIN_GARLAND_COLORS,
+};
+pub use gauge::{GAUGE_FULL, GaugeEffect, GaugeStyle, percent_to_level};
+pub use gradient::{
+    GradientConfig,
+    GradientEffect,
+    MAX_GRADIENT_STOPS,
+    MIN_GRADIENT_STOPS,
+};
+pub use heartbeat::{DEFAULT_BPM, HeartbeatEffect, MAX_BPM, MIN_BPM};
+pub use plasma::PlasmaEffect;
+pub use rain::{MAX_RAIN_DROPS, RainEffect, RainStyle};
 pub use rainbow::RainbowEffect;
+pub use spectrum::SpectrumEffect;
 pub use static_color::StaticColorEffect;
+pub use strobe::{
+    MIN_SAFE_FLASH_INTERVAL,
+    StrobeEffect,
+    StrobePattern,
+    StrobeStyle,
+};
+pub use vu_meter::VuMeterEffect;
 
-use crate::{color::Rgb, effect::rainbow::RainbowVariant};
+use crate::{
+    audio::{AudioLevel, Spectrum},
+    beat::Beat,
+    color::{ColorSlot, Rgb},
+    effect::rainbow::RainbowVariant,
+    time_of_day::DayClock,
+    transition::ColorBlending,
+};
 
 const EFFECT_NAME_STATIC: &str = "static";
 const EFFECT_NAME_FANTASY: &str = "fantasy";
@@ -23,6 +74,19 @@ const EFFECT_NAME_GARLAND: &str = "garland";
 const EFFECT_NAME_NEON: &str = "neon";
 const EFFECT_NAME_REST: &str = "rest";
 const EFFECT_NAME_SUNSET: &str = "sunset";
+const EFFECT_NAME_CIRCADIAN: &str = "circadian";
+const EFFECT_NAME_PLASMA: &str = "plasma";
+const EFFECT_NAME_HEARTBEAT: &str = "heartbeat";
+const EFFECT_NAME_HEARTBEAT_RIPPLE: &str = "heartbeat_ripple";
+const EFFECT_NAME_STROBE_HALVES: &str = "strobe_halves";
+const EFFECT_NAME_STROBE_ALTERNATE: &str = "strobe_alternate";
+const EFFECT_NAME_STROBE_QUAD: &str = "strobe_quad";
+const EFFECT_NAME_GAUGE: &str = "gauge";
+const EFFECT_NAME_CLOCK: &str = "clock";
+const EFFECT_NAME_RAIN: &str = "rain";
+const EFFECT_NAME_GRADIENT: &str = "gradient";
+const EFFECT_NAME_VU_METER: &str = "vu_meter";
+const EFFECT_NAME_SPECTRUM: &str = "spectrum";
 
 const EFFECT_ID_STATIC: u8 = 0;
 const EFFECT_ID_FANTASY: u8 = 1;
@@ -33,6 +97,19 @@ const EFFECT_ID_GARLAND: u8 = 5;
 const EFFECT_ID_NEON: u8 = 6;
 const EFFECT_ID_REST: u8 = 7;
 const EFFECT_ID_SUNSET: u8 = 8;
+const EFFECT_ID_CIRCADIAN: u8 = 9;
+const EFFECT_ID_PLASMA: u8 = 10;
+const EFFECT_ID_HEARTBEAT: u8 = 11;
+const EFFECT_ID_HEARTBEAT_RIPPLE: u8 = 12;
+const EFFECT_ID_STROBE_HALVES: u8 = 13;
+const EFFECT_ID_STROBE_ALTERNATE: u8 = 14;
+const EFFECT_ID_STROBE_QUAD: u8 = 15;
+const EFFECT_ID_GAUGE: u8 = 16;
+const EFFECT_ID_CLOCK: u8 = 17;
+const EFFECT_ID_RAIN: u8 = 18;
+const EFFECT_ID_GRADIENT: u8 = 19;
+const EFFECT_ID_VU_METER: u8 = 20;
+const EFFECT_ID_SPECTRUM: u8 = 21;
 
 pub trait Effect {
     /// Sets if effect requires precise (corrected) colors
@@ -53,7 +130,11 @@ pub trait Effect {
 }
 
 /// Effect slot - enum containing all possible effects
+///
+/// Effects with fixed-capacity state make some variants larger than others;
+/// this is accepted since effects can't be boxed without an allocator.
 #[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
 pub enum EffectSlot {
     /// Mirrored rainbow cycling effect
     Fantasy(RainbowEffect),
@@ -69,6 +150,34 @@ pub enum EffectSlot {
     Rest(FlowEffect),
     /// Sunset effect with flowing gradients
     Sunset(FlowEffect),
+    /// White light following the time of day
+    Circadian(CircadianEffect),
+    /// Plasma effect with summed sine waves
+    Plasma(PlasmaEffect),
+    /// Double pulse heartbeat in the user color
+    Heartbeat(HeartbeatEffect),
+    /// Heartbeat rippling out from the center
+    HeartbeatRipple(HeartbeatEffect),
+    /// Two-color strobe alternating between strip halves
+    StrobeHalves(StrobeEffect),
+    /// Two-color strobe alternating between even and odd LEDs
+    StrobeAlternate(StrobeEffect),
+    /// Two-color quad flash strobe
+    StrobeQuad(StrobeEffect),
+    /// Progress bar filled up to a runtime level
+    Gauge(GaugeEffect),
+    /// Analog clock on an LED ring
+    Clock(ClockEffect),
+    /// Drops falling along the strip and splashing at its end
+    Rain(RainEffect),
+    /// User-defined static or scrolling gradient
+    Gradient(GradientEffect),
+    /// Holiday garland with alternating color groups
+    Garland(GarlandEffect),
+    /// Audio level bar with peak hold
+    VuMeter(VuMeterEffect),
+    /// Audio spectrum with one segment per frequency bin
+    Spectrum(SpectrumEffect),
 }
 
 /// Known effect ids that can be requested.
@@ -84,6 +193,19 @@ pub enum EffectId {
     Neon = EFFECT_ID_NEON,
     Rest = EFFECT_ID_REST,
     Sunset = EFFECT_ID_SUNSET,
+    Circadian = EFFECT_ID_CIRCADIAN,
+    Plasma = EFFECT_ID_PLASMA,
+    Heartbeat = EFFECT_ID_HEARTBEAT,
+    HeartbeatRipple = EFFECT_ID_HEARTBEAT_RIPPLE,
+    StrobeHalves = EFFECT_ID_STROBE_HALVES,
+    StrobeAlternate = EFFECT_ID_STROBE_ALTERNATE,
+    StrobeQuad = EFFECT_ID_STROBE_QUAD,
+    Gauge = EFFECT_ID_GAUGE,
+    Clock = EFFECT_ID_CLOCK,
+    Rain = EFFECT_ID_RAIN,
+    Gradient = EFFECT_ID_GRADIENT,
+    VuMeter = EFFECT_ID_VU_METER,
+    Spectrum = EFFECT_ID_SPECTRUM,
 }
 
 impl Default for EffectSlot {
@@ -104,6 +226,19 @@ impl EffectId {
             EFFECT_ID_NEON => Self::Neon,
             EFFECT_ID_REST => Self::Rest,
             EFFECT_ID_SUNSET => Self::Sunset,
+            EFFECT_ID_CIRCADIAN => Self::Circadian,
+            EFFECT_ID_PLASMA => Self::Plasma,
+            EFFECT_ID_HEARTBEAT => Self::Heartbeat,
+            EFFECT_ID_HEARTBEAT_RIPPLE => Self::HeartbeatRipple,
+            EFFECT_ID_STROBE_HALVES => Self::StrobeHalves,
+            EFFECT_ID_STROBE_ALTERNATE => Self::StrobeAlternate,
+            EFFECT_ID_STROBE_QUAD => Self::StrobeQuad,
+            EFFECT_ID_GAUGE => Self::Gauge,
+            EFFECT_ID_CLOCK => Self::Clock,
+            EFFECT_ID_RAIN => Self::Rain,
+            EFFECT_ID_GRADIENT => Self::Gradient,
+            EFFECT_ID_VU_METER => Self::VuMeter,
+            EFFECT_ID_SPECTRUM => Self::Spectrum,
             _ => return None,
         })
     }
@@ -123,12 +258,31 @@ impl EffectId {
             Self::RainbowLongInverse => EffectSlot::RainbowForward(
                 RainbowEffect::new(RainbowVariant::Long).with_inverse(),
             ),
-            Self::Garland => EffectSlot::RainbowBackward(
-                RainbowEffect::new(RainbowVariant::Short).with_inverse(),
-            ),
+            Self::Garland => EffectSlot::Garland(GarlandEffect::new(color)),
             Self::Neon => EffectSlot::Neon(FlowEffect::new(FlowVariant::Neon)),
             Self::Rest => EffectSlot::Rest(FlowEffect::new(FlowVariant::LavaLamp)),
             Self::Sunset => EffectSlot::Sunset(FlowEffect::new(FlowVariant::Sunset)),
+            Self::Circadian => EffectSlot::Circadian(CircadianEffect::new()),
+            Self::Plasma => EffectSlot::Plasma(PlasmaEffect::new()),
+            Self::Heartbeat => EffectSlot::Heartbeat(HeartbeatEffect::new(color)),
+            Self::HeartbeatRipple => EffectSlot::HeartbeatRipple(
+                HeartbeatEffect::new(color).with_ripple(),
+            ),
+            Self::StrobeHalves => EffectSlot::StrobeHalves(
+                StrobeEffect::new(StrobePattern::Halves).with_color_at(0, color),
+            ),
+            Self::StrobeAlternate => EffectSlot::StrobeAlternate(
+                StrobeEffect::new(StrobePattern::Alternate).with_color_at(0, color),
+            ),
+            Self::StrobeQuad => EffectSlot::StrobeQuad(
+                StrobeEffect::new(StrobePattern::QuadFlash).with_color_at(0, color),
+            ),
+            Self::Gauge => EffectSlot::Gauge(GaugeEffect::new(color)),
+            Self::Clock => EffectSlot::Clock(ClockEffect::new()),
+            Self::Rain => EffectSlot::Rain(RainEffect::new(color)),
+            Self::Gradient => EffectSlot::Gradient(GradientEffect::new(color)),
+            Self::VuMeter => EffectSlot::VuMeter(VuMeterEffect::new()),
+            Self::Spectrum => EffectSlot::Spectrum(SpectrumEffect::new()),
         }
     }
 
@@ -143,6 +297,19 @@ impl EffectId {
             Self::Neon => EFFECT_NAME_NEON,
             Self::Rest => EFFECT_NAME_REST,
             Self::Sunset => EFFECT_NAME_SUNSET,
+            Self::Circadian => EFFECT_NAME_CIRCADIAN,
+            Self::Plasma => EFFECT_NAME_PLASMA,
+            Self::Heartbeat => EFFECT_NAME_HEARTBEAT,
+            Self::HeartbeatRipple => EFFECT_NAME_HEARTBEAT_RIPPLE,
+            Self::StrobeHalves => EFFECT_NAME_STROBE_HALVES,
+            Self::StrobeAlternate => EFFECT_NAME_STROBE_ALTERNATE,
+            Self::StrobeQuad => EFFECT_NAME_STROBE_QUAD,
+            Self::Gauge => EFFECT_NAME_GAUGE,
+            Self::Clock => EFFECT_NAME_CLOCK,
+            Self::Rain => EFFECT_NAME_RAIN,
+            Self::Gradient => EFFECT_NAME_GRADIENT,
+            Self::VuMeter => EFFECT_NAME_VU_METER,
+            Self::Spectrum => EFFECT_NAME_SPECTRUM,
         }
     }
 
@@ -157,6 +324,19 @@ impl EffectId {
             EFFECT_NAME_NEON => Some(Self::Neon),
             EFFECT_NAME_REST => Some(Self::Rest),
             EFFECT_NAME_SUNSET => Some(Self::Sunset),
+            EFFECT_NAME_CIRCADIAN => Some(Self::Circadian),
+            EFFECT_NAME_PLASMA => Some(Self::Plasma),
+            EFFECT_NAME_HEARTBEAT => Some(Self::Heartbeat),
+            EFFECT_NAME_HEARTBEAT_RIPPLE => Some(Self::HeartbeatRipple),
+            EFFECT_NAME_STROBE_HALVES => Some(Self::StrobeHalves),
+            EFFECT_NAME_STROBE_ALTERNATE => Some(Self::StrobeAlternate),
+            EFFECT_NAME_STROBE_QUAD => Some(Self::StrobeQuad),
+            EFFECT_NAME_GAUGE => Some(Self::Gauge),
+            EFFECT_NAME_CLOCK => Some(Self::Clock),
+            EFFECT_NAME_RAIN => Some(Self::Rain),
+            EFFECT_NAME_GRADIENT => Some(Self::Gradient),
+            EFFECT_NAME_VU_METER => Some(Self::VuMeter),
+            EFFECT_NAME_SPECTRUM => Some(Self::Spectrum),
             _ => None,
         }
     }
@@ -176,6 +356,21 @@ impl EffectSlot {
             Self::Neon(_) | Self::Rest(_) | Self::Sunset(_) => {
                 FlowEffect::PRECISE_COLORS
             }
+            Self::Circadian(_) => CircadianEffect::PRECISE_COLORS,
+            Self::Plasma(_) => PlasmaEffect::PRECISE_COLORS,
+            Self::Heartbeat(_) | Self::HeartbeatRipple(_) => {
+                HeartbeatEffect::PRECISE_COLORS
+            }
+            Self::StrobeHalves(_)
+            | Self::StrobeAlternate(_)
+            | Self::StrobeQuad(_) => StrobeEffect::PRECISE_COLORS,
+            Self::Gauge(_) => GaugeEffect::PRECISE_COLORS,
+            Self::Clock(_) => ClockEffect::PRECISE_COLORS,
+            Self::Rain(_) => RainEffect::PRECISE_COLORS,
+            Self::Gradient(_) => GradientEffect::PRECISE_COLORS,
+            Self::Garland(_) => GarlandEffect::PRECISE_COLORS,
+            Self::VuMeter(_) => VuMeterEffect::PRECISE_COLORS,
+            Self::Spectrum(_) => SpectrumEffect::PRECISE_COLORS,
         }
     }
 
@@ -189,6 +384,21 @@ impl EffectSlot {
             Self::Neon(effect) | Self::Rest(effect) | Self::Sunset(effect) => {
                 effect.render(now, leds);
             }
+            Self::Circadian(effect) => effect.render(now, leds),
+            Self::Plasma(effect) => effect.render(now, leds),
+            Self::Heartbeat(effect) | Self::HeartbeatRipple(effect) => {
+                effect.render(now, leds);
+            }
+            Self::StrobeHalves(effect)
+            | Self::StrobeAlternate(effect)
+            | Self::StrobeQuad(effect) => effect.render(now, leds),
+            Self::Gauge(effect) => effect.render(now, leds),
+            Self::Clock(effect) => effect.render(now, leds),
+            Self::Rain(effect) => effect.render(now, leds),
+            Self::Gradient(effect) => effect.render(now, leds),
+            Self::Garland(effect) => effect.render(now, leds),
+            Self::VuMeter(effect) => effect.render(now, leds),
+            Self::Spectrum(effect) => effect.render(now, leds),
         }
     }
 
@@ -202,6 +412,21 @@ impl EffectSlot {
             Self::Neon(effect) | Self::Rest(effect) | Self::Sunset(effect) => {
                 Effect::reset(effect);
             }
+            Self::Circadian(effect) => Effect::reset(effect),
+            Self::Plasma(effect) => Effect::reset(effect),
+            Self::Heartbeat(effect) | Self::HeartbeatRipple(effect) => {
+                Effect::reset(effect);
+            }
+            Self::StrobeHalves(effect)
+            | Self::StrobeAlternate(effect)
+            | Self::StrobeQuad(effect) => Effect::reset(effect),
+            Self::Gauge(effect) => Effect::reset(effect),
+            Self::Clock(effect) => Effect::reset(effect),
+            Self::Rain(effect) => Effect::reset(effect),
+            Self::Gradient(effect) => Effect::reset(effect),
+            Self::Garland(effect) => Effect::reset(effect),
+            Self::VuMeter(effect) => Effect::reset(effect),
+            Self::Spectrum(effect) => Effect::reset(effect),
         }
     }
 
@@ -215,14 +440,249 @@ impl EffectSlot {
             Self::Neon(_) => EffectId::Neon,
             Self::Rest(_) => EffectId::Rest,
             Self::Sunset(_) => EffectId::Sunset,
+            Self::Circadian(_) => EffectId::Circadian,
+            Self::Plasma(_) => EffectId::Plasma,
+            Self::Heartbeat(_) => EffectId::Heartbeat,
+            Self::HeartbeatRipple(_) => EffectId::HeartbeatRipple,
+            Self::StrobeHalves(_) => EffectId::StrobeHalves,
+            Self::StrobeAlternate(_) => EffectId::StrobeAlternate,
+            Self::StrobeQuad(_) => EffectId::StrobeQuad,
+            Self::Gauge(_) => EffectId::Gauge,
+            Self::Clock(_) => EffectId::Clock,
+            Self::Rain(_) => EffectId::Rain,
+            Self::Gradient(_) => EffectId::Gradient,
+            Self::Garland(_) => EffectId::Garland,
+            Self::VuMeter(_) => EffectId::VuMeter,
+            Self::Spectrum(_) => EffectId::Spectrum,
         }
     }
 
     /// Update the color of the current effect with optional transition.
-    #[allow(clippy::single_match)]
     pub fn set_color(&mut self, color: Rgb, duration: Duration, now: Instant) {
         match self {
             Self::Static(effect) => effect.set_color(color, duration, now),
+            Self::Heartbeat(effect) | Self::HeartbeatRipple(effect) => {
+                effect.set_color(color, duration, now);
+            }
+            Self::Gauge(effect) => effect.set_color(color, duration, now),
+            Self::Rain(effect) => effect.set_color(color, duration, now),
+            Self::Garland(effect) => effect.set_color(color, duration, now),
+            Self::StrobeHalves(effect)
+            | Self::StrobeAlternate(effect)
+            | Self::StrobeQuad(effect) => {
+                effect.set_color_at(0, color, duration, now);
+            }
+            Self::Clock(effect) => effect.set_color_at(0, color, duration, now),
+            Self::Gradient(effect) => effect.set_color_at(0, color, duration, now),
+            _ => {}
+        }
+    }
+
+    /// Update the level shown by the gauge effect.
+    #[allow(clippy::single_match)]
+    pub fn set_level(&mut self, level: u16, now: Instant) {
+        match self {
+            Self::Gauge(effect) => effect.set_level(level, now),
+            _ => {}
+        }
+    }
+
+    /// Update the style of the gauge effect.
+    #[allow(clippy::single_match)]
+    pub fn set_gauge_style(&mut self, style: GaugeStyle) {
+        match self {
+            Self::Gauge(effect) => effect.set_style(style),
+            _ => {}
+        }
+    }
+
+    /// Update the flash rate of the strobe effects.
+    pub fn set_strobe_style(&mut self, style: StrobeStyle) {
+        match self {
+            Self::StrobeHalves(effect)
+            | Self::StrobeAlternate(effect)
+            | Self::StrobeQuad(effect) => effect.set_style(style),
+            _ => {}
+        }
+    }
+
+    /// Update the style of the rain effect.
+    #[allow(clippy::single_match)]
+    pub fn set_rain_style(&mut self, style: RainStyle) {
+        match self {
+            Self::Rain(effect) => effect.set_style(style),
+            _ => {}
+        }
+    }
+
+    /// Update the palette of flow effects, crossfading over `duration`.
+    ///
+    /// `None` restores the built-in palette of the effect.
+    pub fn set_flow_palette(
+        &mut self,
+        palette: Option<&FlowPalette>,
+        duration: Duration,
+        now: Instant,
+    ) {
+        match self {
+            Self::Neon(effect) | Self::Rest(effect) | Self::Sunset(effect) => {
+                effect.set_palette(palette.cloned(), duration, now);
+            }
+            _ => {}
+        }
+    }
+
+    /// Update a color slot of the current effect with optional transition.
+    ///
+    /// The primary slot is the color of single-color effects. Multi-color
+    /// effects (garland, strobe, gauge, clock and gradient) take further
+    /// colors from the other slots.
+    pub fn set_slot_color(
+        &mut self,
+        slot: ColorSlot,
+        color: Rgb,
+        duration: Duration,
+        now: Instant,
+    ) {
+        match (slot, self) {
+            (ColorSlot::Primary, effect) => effect.set_color(color, duration, now),
+            (slot, Self::Garland(effect)) => {
+                effect.set_color_at(slot.index(), color, duration, now);
+            }
+            (slot, Self::Gauge(effect)) => {
+                effect.set_color_at(slot.index(), color, duration, now);
+            }
+            (
+                slot,
+                Self::StrobeHalves(effect)
+                | Self::StrobeAlternate(effect)
+                | Self::StrobeQuad(effect),
+            ) => effect.set_color_at(slot.index(), color, duration, now),
+            (slot, Self::Clock(effect)) => {
+                effect.set_color_at(slot.index(), color, duration, now);
+            }
+            (slot, Self::Gradient(effect)) => {
+                effect.set_color_at(slot.index(), color, duration, now);
+            }
+            _ => {}
+        }
+    }
+
+    /// Feed the current audio level to audio-reactive effects.
+    #[allow(clippy::single_match)]
+    pub fn set_audio_level(&mut self, level: AudioLevel, now: Instant) {
+        match self {
+            Self::VuMeter(effect) => effect.set_level(level, now),
+            _ => {}
+        }
+    }
+
+    /// Feed the current frequency spectrum to audio-reactive effects.
+    #[allow(clippy::single_match)]
+    pub fn set_spectrum(&mut self, spectrum: &Spectrum) {
+        match self {
+            Self::Spectrum(effect) => effect.set_spectrum(spectrum),
+            _ => {}
+        }
+    }
+
+    /// Set the color space used for color transitions and crossfades.
+    pub fn set_color_blending(&mut self, blending: ColorBlending) {
+        match self {
+            Self::Static(effect) => effect.set_blending(blending),
+            Self::Garland(effect) => effect.set_blending(blending),
+            Self::Gauge(effect) => effect.set_blending(blending),
+            Self::StrobeHalves(effect)
+            | Self::StrobeAlternate(effect)
+            | Self::StrobeQuad(effect) => effect.set_blending(blending),
+            Self::Clock(effect) => effect.set_blending(blending),
+            Self::Gradient(effect) => effect.set_blending(blending),
+            Self::Neon(effect) | Self::Rest(effect) | Self::Sunset(effect) => {
+                effect.set_blending(blending);
+            }
+            _ => {}
+        }
+    }
+
+    /// Follow the beat in effects that support it, `None` disables it.
+    pub fn set_beat(&mut self, beat: Option<Beat>) {
+        match self {
+            Self::Fantasy(effect)
+            | Self::RainbowForward(effect)
+            | Self::RainbowBackward(effect) => effect.set_beat(beat),
+            Self::Neon(effect) | Self::Rest(effect) | Self::Sunset(effect) => {
+                effect.set_beat(beat);
+            }
+            _ => {}
+        }
+    }
+
+    /// Update the colors of the garland effect.
+    #[allow(clippy::single_match)]
+    pub fn set_garland_colors(
+        &mut self,
+        colors: &GarlandColors,
+        duration: Duration,
+        now: Instant,
+    ) {
+        match self {
+            Self::Garland(effect) => effect.set_colors(colors, duration, now),
+            _ => {}
+        }
+    }
+
+    /// Update the style of the garland effect.
+    #[allow(clippy::single_match)]
+    pub fn set_garland_style(&mut self, style: GarlandStyle) {
+        match self {
+            Self::Garland(effect) => effect.set_style(style),
+            _ => {}
+        }
+    }
+
+    /// Update the stops of the gradient effect.
+    #[allow(clippy::single_match)]
+    pub fn set_gradient(&mut self, config: &GradientConfig) {
+        match self {
+            Self::Gradient(effect) => effect.set_config(config.clone()),
+            _ => {}
+        }
+    }
+
+    /// Update the tempo of beat-driven effects.
+    #[allow(clippy::single_match)]
+    pub fn set_bpm(&mut self, bpm: u16) {
+        match self {
+            Self::Heartbeat(effect) | Self::HeartbeatRipple(effect) => {
+                effect.set_bpm(bpm);
+            }
+            _ => {}
+        }
+    }
+
+    /// Update the clock used by time-of-day aware effects.
+    pub fn set_clock(&mut self, clock: DayClock) {
+        match self {
+            Self::Circadian(effect) => effect.set_clock(clock),
+            Self::Clock(effect) => effect.set_clock(clock),
+            _ => {}
+        }
+    }
+
+    /// Update the style of the clock effect.
+    #[allow(clippy::single_match)]
+    pub fn set_clock_style(&mut self, style: ClockStyle) {
+        match self {
+            Self::Clock(effect) => effect.set_style(style),
+            _ => {}
+        }
+    }
+
+    /// Update the daily curve of the circadian effect.
+    #[allow(clippy::single_match)]
+    pub fn set_circadian_curve(&mut self, curve: &CircadianCurve) {
+        match self {
+            Self::Circadian(effect) => effect.set_curve(curve.clone()),
             _ => {}
         }
     }
@@ -230,12 +690,27 @@ impl EffectSlot {
     pub fn is_transitioning(&self) -> bool {
         match self {
             Self::Static(effect) => effect.is_transitioning(),
+            Self::Heartbeat(effect) | Self::HeartbeatRipple(effect) => {
+                effect.is_transitioning()
+            }
             Self::Fantasy(_)
             | Self::RainbowForward(_)
             | Self::RainbowBackward(_)
-            | Self::Neon(_)
-            | Self::Rest(_)
-            | Self::Sunset(_) => false,
+            | Self::Circadian(_)
+            | Self::Plasma(_)
+            | Self::VuMeter(_)
+            | Self::Spectrum(_) => false,
+            Self::StrobeHalves(effect)
+            | Self::StrobeAlternate(effect)
+            | Self::StrobeQuad(effect) => effect.is_transitioning(),
+            Self::Clock(effect) => effect.is_transitioning(),
+            Self::Gradient(effect) => effect.is_transitioning(),
+            Self::Neon(effect) | Self::Rest(effect) | Self::Sunset(effect) => {
+                effect.is_transitioning()
+            }
+            Self::Gauge(effect) => effect.is_transitioning(),
+            Self::Rain(effect) => effect.is_transitioning(),
+            Self::Garland(effect) => effect.is_transitioning(),
         }
     }
 }
@@ -0,0 +1,112 @@
+//! Plasma effect built on summed sine waves
+
+use embassy_time::Instant;
+
+use super::Effect;
+use crate::{
+    color::{Palette, Rgb, hex_palette},
+    math8::{cos8, sin8},
+};
+
+// Plasma palette: violet → magenta → orange → yellow → cyan → blue → violet.
+// First and last colors match so the palette can wrap without a seam.
+#[allow(clippy::unreadable_literal)]
+const PLASMA_COLORS: [Rgb; 7] = hex_palette![
+    0x3A00B0, // Violet
+    0xFF0070, // Magenta
+    0xFF6A00, // Orange
+    0xFFE000, // Yellow
+    0x00E0FF, // Cyan
+    0x0030FF, // Blue
+    0x3A00B0  // Violet
+];
+
+const PLASMA_PALETTE: Palette =
+    Palette::StaticStops(&Palette::spread_stops(PLASMA_COLORS));
+
+// Wavelengths (in LEDs) of the summed waves
+const WAVE1_LEDS: u32 = 16;
+const WAVE2_LEDS: u32 = 23;
+const WAVE3_LEDS: u32 = 37;
+
+// Time for each wave to travel one wavelength
+const WAVE1_PERIOD_MS: u64 = 3_000;
+const WAVE2_PERIOD_MS: u64 = 4_700;
+const WAVE3_PERIOD_MS: u64 = 7_100;
+
+/// Time for the palette to shift through one full cycle
+const PALETTE_PERIOD_MS: u64 = 20_000;
+
+/// Plasma effect
+///
+/// Sums three moving sine waves of different wavelengths and maps the result
+/// through a palette. Compared to [`FlowEffect`](super::FlowEffect), the
+/// waves give sharper and faster moving color bands.
+#[derive(Debug, Clone)]
+pub struct PlasmaEffect {
+    palette: Palette,
+}
+
+impl Default for PlasmaEffect {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PlasmaEffect {
+    /// Create a new plasma effect with the default palette
+    pub const fn new() -> Self {
+        Self {
+            palette: PLASMA_PALETTE,
+        }
+    }
+
+    /// Set the palette
+    ///
+    /// The palette index wraps around, so palettes whose first and last
+    /// colors match look seamless.
+    #[must_use]
+    pub fn with_palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Phase (0-255) of a wave with the given period at `time_ms`
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    const fn phase(time_ms: u64, period_ms: u64) -> u8 {
+        (((time_ms % period_ms) << 8) / period_ms) as u8
+    }
+
+    /// Angle (0-255) of LED `i` on a wave with the given wavelength
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    const fn angle(i: u32, wavelength: u32) -> u8 {
+        ((i << 8) / wavelength) as u8
+    }
+}
+
+impl Effect for PlasmaEffect {
+    #[allow(clippy::cast_possible_truncation)]
+    fn render(&mut self, now: Instant, leds: &mut [Rgb]) {
+        let time_ms = now.as_millis();
+        let phase1 = Self::phase(time_ms, WAVE1_PERIOD_MS);
+        let phase2 = Self::phase(time_ms, WAVE2_PERIOD_MS);
+        let phase3 = Self::phase(time_ms, WAVE3_PERIOD_MS);
+        let shift = Self::phase(time_ms, PALETTE_PERIOD_MS);
+
+        // The third wave wobbles instead of travelling at a constant speed
+        let wobble = sin8(phase3);
+
+        for (i, led) in leds.iter_mut().enumerate() {
+            let i = u32::try_from(i).unwrap_or(u32::MAX);
+            let w1 = sin8(Self::angle(i, WAVE1_LEDS).wrapping_add(phase1));
+            let w2 = sin8(Self::angle(i, WAVE2_LEDS).wrapping_sub(phase2));
+            let w3 = cos8(Self::angle(i, WAVE3_LEDS).wrapping_add(wobble));
+
+            let sum = (u16::from(w1) + u16::from(w2) + u16::from(w3)) / 3;
+            let index = (sum as u8).wrapping_add(shift);
+            *led = self.palette.sample(index);
+        }
+    }
+}
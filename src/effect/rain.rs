@@ -0,0 +1,262 @@
+//! Rain / drip effect
+//!
+//! Drops form at the start of the strip, accelerate towards its end and
+//! splash with a short flash. Drops live in a fixed-capacity list, so memory
+//! use is bounded regardless of density.
+
+use embassy_time::{Duration, Instant};
+use heapless::Vec;
+
+use super::Effect;
+use crate::{
+    color::{Rgb, blend_colors},
+    math8::scale8,
+    transition::ValueTransition,
+};
+
+/// Maximum number of drops alive at the same time
+pub const MAX_RAIN_DROPS: usize = 8;
+
+/// Time for a drop to form before it falls
+const FORM_MS: u64 = 400;
+/// Duration of the splash flash
+const SPLASH_MS: u64 = 180;
+/// Acceleration at default speed, in 8.8 fixed-point LEDs per second squared
+const GRAVITY_FP: u64 = 83 << 8;
+/// Speed value that yields `GRAVITY_FP`
+const DEFAULT_SPEED: u8 = 128;
+/// Length of the fading tail behind a falling drop, in LEDs
+const TAIL_LEDS: u32 = 3;
+/// Longest fall time computed, longer than the slowest drop takes for 255
+/// LEDs, so long pauses between renders can't overflow the fall distance
+const MAX_FALL_MS: u64 = 60_000;
+
+/// Density and speed of the rain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RainStyle {
+    /// Average number of drops per 10 seconds
+    pub density: u8,
+    /// Fall acceleration (128 = default, 255 = about twice as fast)
+    ///
+    /// 0 is treated as 1, so drops always reach the end of the strip.
+    pub speed: u8,
+}
+
+impl RainStyle {
+    /// Clamp the speed to the slowest one that still moves
+    const fn moving(self) -> Self {
+        Self {
+            speed: if self.speed == 0 { 1 } else { self.speed },
+            ..self
+        }
+    }
+}
+
+impl Default for RainStyle {
+    fn default() -> Self {
+        Self {
+            density: 12,
+            speed: DEFAULT_SPEED,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RainDrop {
+    /// Time the drop started forming
+    spawned_at: u64,
+    /// Per-drop speed variation (128 = nominal)
+    speed: u8,
+    /// Time the drop hit the end of the strip
+    splashed_at: Option<u64>,
+}
+
+/// Rain effect in the user color
+#[derive(Debug, Clone)]
+pub struct RainEffect {
+    color: ValueTransition<Rgb>,
+    style: RainStyle,
+    drops: Vec<RainDrop, MAX_RAIN_DROPS>,
+    next_spawn: Option<u64>,
+    seed: u32,
+}
+
+impl RainEffect {
+    /// Create a new rain effect
+    pub fn new(color: Rgb) -> Self {
+        Self {
+            color: ValueTransition::new_rgb(color),
+            style: RainStyle::default(),
+            drops: Vec::new(),
+            next_spawn: None,
+            seed: 0x2545_f491,
+        }
+    }
+
+    /// Set the style
+    #[must_use]
+    pub fn with_style(mut self, style: RainStyle) -> Self {
+        self.style = style.moving();
+        self
+    }
+
+    /// Replace the style
+    pub fn set_style(&mut self, style: RainStyle) {
+        self.style = style.moving();
+        self.next_spawn = None;
+    }
+
+    /// Set the color with smooth transition
+    pub fn set_color(&mut self, color: Rgb, duration: Duration, now: Instant) {
+        self.color.set(color, duration, now);
+    }
+
+    /// Number of drops currently alive
+    pub fn drop_count(&self) -> usize {
+        self.drops.len()
+    }
+
+    /// Next pseudo-random value (xorshift32)
+    fn random(&mut self) -> u32 {
+        let mut x = self.seed;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.seed = x;
+        x
+    }
+
+    /// Random delay until the next drop, between 0.5 and 1.5 mean intervals
+    fn spawn_delay(&mut self) -> Option<u64> {
+        if self.style.density == 0 {
+            return None;
+        }
+        let mean = 10_000 / u64::from(self.style.density);
+        let jitter = u64::from(self.random() % 1024);
+        Some(mean / 2 + mean * jitter / 1024)
+    }
+
+    /// Distance fallen `elapsed_ms` after release, in 8.8 fixed-point LEDs
+    fn fallen(&self, drop: &RainDrop, elapsed_ms: u64) -> u64 {
+        let accel = GRAVITY_FP * u64::from(self.style.speed) * u64::from(drop.speed)
+            / (u64::from(DEFAULT_SPEED) * 128);
+        let elapsed_ms = elapsed_ms.min(MAX_FALL_MS);
+        accel * elapsed_ms * elapsed_ms / 2_000_000
+    }
+
+    /// Spawn new drops and retire finished ones
+    #[allow(clippy::cast_possible_truncation)]
+    fn update(&mut self, time_ms: u64, len: usize) {
+        if self.next_spawn.is_none() {
+            self.next_spawn = self.spawn_delay().map(|delay| time_ms + delay);
+        }
+        if self.next_spawn.is_some_and(|next| time_ms >= next) {
+            // Slots are limited; when full the drop is skipped
+            let speed = 96 + (self.random() % 64) as u8;
+            let _ = self.drops.push(RainDrop {
+                spawned_at: time_ms,
+                speed,
+                splashed_at: None,
+            });
+            self.next_spawn = self.spawn_delay().map(|delay| time_ms + delay);
+        }
+
+        let end = (len.saturating_sub(1) as u64) << 8;
+        for index in 0..self.drops.len() {
+            let drop = self.drops[index];
+            if drop.splashed_at.is_some() {
+                continue;
+            }
+            let released = drop.spawned_at + FORM_MS;
+            if time_ms > released && self.fallen(&drop, time_ms - released) >= end {
+                self.drops[index].splashed_at = Some(time_ms);
+            }
+        }
+        self.drops.retain(|drop| {
+            drop.splashed_at
+                .is_none_or(|splashed| time_ms < splashed + SPLASH_MS)
+        });
+    }
+
+    /// Add `level` of `color` to an LED
+    fn add(led: &mut Rgb, color: Rgb, level: u8) {
+        led.r = led.r.saturating_add(scale8(color.r, level));
+        led.g = led.g.saturating_add(scale8(color.g, level));
+        led.b = led.b.saturating_add(scale8(color.b, level));
+    }
+
+    /// Draw a single drop
+    #[allow(clippy::cast_possible_truncation)]
+    fn draw(&self, drop: &RainDrop, time_ms: u64, color: Rgb, leds: &mut [Rgb]) {
+        let last = leds.len() - 1;
+        let elapsed = time_ms - drop.spawned_at;
+
+        if let Some(splashed) = drop.splashed_at {
+            // Short flash towards white, fading out
+            let fade = 255 - ((time_ms - splashed) * 255 / SPLASH_MS) as u8;
+            let white = Rgb {
+                r: 255,
+                g: 255,
+                b: 255,
+            };
+            let flash = blend_colors(color, white, 128);
+            Self::add(&mut leds[last], flash, fade);
+            if last > 0 {
+                Self::add(&mut leds[last - 1], color, fade / 2);
+            }
+            return;
+        }
+
+        if elapsed < FORM_MS {
+            // Drop swells at the start of the strip
+            let level = (elapsed * 255 / FORM_MS) as u8;
+            Self::add(&mut leds[0], color, scale8(level, level));
+            return;
+        }
+
+        let position = self.fallen(drop, elapsed - FORM_MS);
+        let head = (position >> 8) as usize;
+        let frac = (position & 0xFF) as u8;
+        // Head is anti-aliased between two LEDs, the tail fades behind it
+        if head < last {
+            Self::add(&mut leds[head + 1], color, frac);
+        }
+        for step in 0..=TAIL_LEDS as usize {
+            let Some(index) = head.checked_sub(step) else {
+                break;
+            };
+            let level = 255 - (step as u32 * 255 / (TAIL_LEDS + 1)) as u8;
+            let level = if step == 0 { 255 - frac } else { level };
+            Self::add(&mut leds[index.min(last)], color, scale8(level, level));
+        }
+    }
+}
+
+impl Effect for RainEffect {
+    const PRECISE_COLORS: bool = true;
+
+    fn render(&mut self, now: Instant, leds: &mut [Rgb]) {
+        self.color.tick(now);
+        leds.fill(Rgb { r: 0, g: 0, b: 0 });
+        if leds.is_empty() {
+            return;
+        }
+
+        let time_ms = now.as_millis();
+        self.update(time_ms, leds.len());
+
+        let color = self.color.current();
+        for drop in &self.drops {
+            self.draw(drop, time_ms, color, leds);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.drops.clear();
+        self.next_spawn = None;
+    }
+
+    fn is_transitioning(&self) -> bool {
+        self.color.is_transitioning()
+    }
+}
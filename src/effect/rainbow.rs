@@ -9,12 +9,15 @@ use embassy_time::{Duration, Instant};
 
 use super::Effect;
 use crate::{
+    beat::Beat,
     bounds::center_of,
     color::{Hsv, Rgb, fill_gradient_three_fp, mirror_half},
 };
 
 const DEFAULT_CYCLE_MS: u64 = 12_000;
 const HUE_STEP: u8 = 60;
+/// Hue jump on every beat
+const BEAT_HUE_JUMP: u8 = 40;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RainbowVariant {
@@ -39,6 +42,8 @@ pub struct RainbowEffect {
     variant: RainbowVariant,
     /// Inverse direction
     inverse: bool,
+    /// Beat to jump the hue on, if beat sync is enabled
+    beat: Option<Beat>,
 }
 
 impl RainbowEffect {
@@ -50,6 +55,7 @@ impl RainbowEffect {
             saturation: 255,
             variant,
             inverse: false,
+            beat: None,
         }
     }
 
@@ -80,6 +86,11 @@ impl RainbowEffect {
         self.saturation = saturation;
         self
     }
+
+    /// Follow the beat with hue jumps, `None` disables it
+    pub fn set_beat(&mut self, beat: Option<Beat>) {
+        self.beat = beat;
+    }
 }
 
 impl Effect for RainbowEffect {
@@ -92,6 +103,10 @@ impl Effect for RainbowEffect {
         let progress_ms = now.as_millis() % cycle_ms;
         #[allow(clippy::cast_possible_truncation)]
         let base_hue = ((progress_ms * 255) / cycle_ms) as u8;
+        #[allow(clippy::cast_possible_truncation)]
+        let base_hue = self.beat.map_or(base_hue, |beat| {
+            base_hue.wrapping_add((beat.count as u8).wrapping_mul(BEAT_HUE_JUMP))
+        });
 
         let c1 = Hsv {
             hue: base_hue,
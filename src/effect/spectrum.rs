@@ -0,0 +1,153 @@
+//! Audio spectrum analyzer
+//!
+//! Splits the rendering bounds into one segment per frequency bin, low
+//! frequencies first. Each segment glows in its own hue with the smoothed
+//! bin level, and a falling peak leaves a dim afterglow.
+
+use embassy_time::Instant;
+
+use super::Effect;
+use crate::{
+    audio::{MAX_SPECTRUM_BINS, Spectrum},
+    color::{Hsv, Rgb, hsv2rgb},
+    math8::{blend8, scale8},
+};
+
+/// Hue range covered from the lowest to the highest bin
+const HUE_SPAN: u8 = 200;
+/// Time for a rising level to follow its target
+const ATTACK_MS: u64 = 25;
+/// Time for a falling level to follow its target
+const RELEASE_MS: u64 = 100;
+/// Fall rate of the peaks, in level units per second
+const PEAK_FALL_PER_SEC: u64 = 160;
+/// Brightness of the peak afterglow
+const PEAK_GLOW: u8 = 96;
+/// Lowest auto-gain reference, keeps noise from filling the display
+const MIN_GAIN_REF: u32 = 64;
+/// Auto-gain reference release per update, as a fraction of 1/256
+const GAIN_RELEASE: u32 = 2;
+
+/// Spectrum analyzer effect
+#[derive(Debug, Clone)]
+pub struct SpectrumEffect {
+    bin_count: usize,
+    /// Bin levels of the latest spectrum (0-255)
+    targets: [u8; MAX_SPECTRUM_BINS],
+    /// Smoothed bin levels (0-255)
+    levels: [u8; MAX_SPECTRUM_BINS],
+    /// Falling peaks in 8.8 fixed-point
+    peaks: [u16; MAX_SPECTRUM_BINS],
+    /// Magnitude mapped to full scale
+    gain_ref: u32,
+    last_frame: Option<Instant>,
+}
+
+impl Default for SpectrumEffect {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpectrumEffect {
+    /// Create a new silent analyzer
+    pub const fn new() -> Self {
+        Self {
+            bin_count: 0,
+            targets: [0; MAX_SPECTRUM_BINS],
+            levels: [0; MAX_SPECTRUM_BINS],
+            peaks: [0; MAX_SPECTRUM_BINS],
+            gain_ref: MIN_GAIN_REF,
+            last_frame: None,
+        }
+    }
+
+    /// Feed a new spectrum
+    ///
+    /// Magnitudes are scaled against a reference that follows the loudest
+    /// bin up instantly and relaxes slowly. The displayed levels follow the
+    /// new targets while rendering.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn set_spectrum(&mut self, spectrum: &Spectrum) {
+        let bins = spectrum.bins();
+        if bins.len() != self.bin_count {
+            self.bin_count = bins.len();
+            self.targets = [0; MAX_SPECTRUM_BINS];
+            self.levels = [0; MAX_SPECTRUM_BINS];
+            self.peaks = [0; MAX_SPECTRUM_BINS];
+        }
+
+        let loudest = bins.iter().copied().max().map_or(0, u32::from);
+        let relaxed = self.gain_ref - self.gain_ref * GAIN_RELEASE / 256;
+        self.gain_ref = relaxed.max(loudest).max(MIN_GAIN_REF);
+
+        for (target, magnitude) in self.targets.iter_mut().zip(bins) {
+            *target = (u32::from(*magnitude) * 255 / self.gain_ref) as u8;
+        }
+    }
+
+    /// Get the smoothed levels of the bins (0-255)
+    pub fn levels(&self) -> &[u8] {
+        &self.levels[..self.bin_count]
+    }
+
+    /// Get the current peak of a bin (0-255)
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn peak(&self, bin: usize) -> u8 {
+        self.peaks.get(bin).map_or(0, |peak| (peak >> 8) as u8)
+    }
+
+    /// Move the levels towards their targets and let the peaks fall
+    #[allow(clippy::cast_possible_truncation)]
+    fn decay(&mut self, now: Instant) {
+        let elapsed_ms = self
+            .last_frame
+            .map_or(0, |last| now.saturating_duration_since(last).as_millis());
+        self.last_frame = Some(now);
+        if elapsed_ms == 0 {
+            return;
+        }
+
+        let attack = (elapsed_ms * 255 / ATTACK_MS).min(255) as u8;
+        let release = (elapsed_ms * 255 / RELEASE_MS).min(255) as u8;
+        for (level, target) in self.levels.iter_mut().zip(self.targets) {
+            *level = if target >= *level {
+                blend8(*level, target, attack)
+            } else {
+                // Rounding alone would stall just above the target
+                blend8(*level, target, release).min(*level - 1)
+            };
+        }
+
+        let fall = (elapsed_ms * PEAK_FALL_PER_SEC * 256 / 1_000).min(0xFFFF) as u16;
+        for (peak, level) in self.peaks.iter_mut().zip(self.levels) {
+            *peak = peak.saturating_sub(fall).max(u16::from(level) << 8);
+        }
+    }
+}
+
+impl Effect for SpectrumEffect {
+    #[allow(clippy::cast_possible_truncation)]
+    fn render(&mut self, now: Instant, leds: &mut [Rgb]) {
+        self.decay(now);
+        let len = leds.len();
+        if self.bin_count == 0 {
+            leds.fill(Rgb { r: 0, g: 0, b: 0 });
+            return;
+        }
+
+        for (i, led) in leds.iter_mut().enumerate() {
+            let bin = i * self.bin_count / len;
+            let glow = scale8(self.peak(bin), PEAK_GLOW);
+            *led = hsv2rgb(Hsv {
+                hue: (bin * usize::from(HUE_SPAN) / self.bin_count) as u8,
+                sat: 255,
+                val: self.levels[bin].max(glow),
+            });
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
@@ -6,7 +6,10 @@
 use embassy_time::{Duration, Instant};
 
 use super::Effect;
-use crate::{color::Rgb, transition::ValueTransition};
+use crate::{
+    color::Rgb,
+    transition::{ColorBlending, ValueTransition},
+};
 
 /// Static color effect - fills all LEDs with one color
 ///
@@ -33,6 +36,11 @@ impl StaticColorEffect {
     pub fn set_color(&mut self, color: Rgb, duration: Duration, now: Instant) {
         self.color.set(color, duration, now);
     }
+
+    /// Set the color space used for color transitions
+    pub fn set_blending(&mut self, blending: ColorBlending) {
+        self.color.set_blender(blending.blender());
+    }
 }
 
 impl Effect for StaticColorEffect {
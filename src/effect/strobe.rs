@@ -0,0 +1,228 @@
+//! Emergency / police style alternating strobe
+//!
+//! Flashes two colors in alternating patterns. The flash rate is limited to
+//! stay below the 3 Hz photosensitivity threshold unless the limit is
+//! explicitly lifted with [`StrobeStyle::unsafe_rate`].
+
+use embassy_time::{Duration, Instant};
+
+use super::Effect;
+use crate::{
+    bounds::center_of,
+    color::Rgb,
+    transition::{ColorBlending, ValueTransition},
+};
+
+/// Shortest interval between flashes allowed in safe mode (2.5 Hz)
+pub const MIN_SAFE_FLASH_INTERVAL: Duration = Duration::from_millis(400);
+
+/// Default interval between flashes (2 Hz)
+const DEFAULT_FLASH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Shortest interval between flashes without the limit
+///
+/// Each interval needs a lit and a dark millisecond.
+const MIN_UNSAFE_FLASH_INTERVAL: Duration = Duration::from_millis(2);
+
+/// Number of flashes per side in the quad flash pattern
+const QUAD_FLASHES: u64 = 4;
+
+const RED: Rgb = Rgb { r: 255, g: 0, b: 0 };
+const BLUE: Rgb = Rgb { r: 0, g: 0, b: 255 };
+const BLACK: Rgb = Rgb { r: 0, g: 0, b: 0 };
+
+/// Strobe flash pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrobePattern {
+    /// First half flashes the first color, second half the second color
+    Halves,
+    /// Even LEDs flash the first color, odd LEDs the second color
+    Alternate,
+    /// Each half flashes four times before handing over to the other half
+    QuadFlash,
+}
+
+/// Flash rate of the strobe
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StrobeStyle {
+    /// Interval between flashes
+    ///
+    /// In safe mode the interval is clamped to [`MIN_SAFE_FLASH_INTERVAL`].
+    pub interval: Duration,
+    /// Lift the flash rate limit
+    ///
+    /// Flashing faster than 3 Hz can trigger seizures in people with
+    /// photosensitive epilepsy. Only use this where that risk is acceptable.
+    pub unsafe_rate: bool,
+}
+
+impl Default for StrobeStyle {
+    fn default() -> Self {
+        Self {
+            interval: DEFAULT_FLASH_INTERVAL,
+            unsafe_rate: false,
+        }
+    }
+}
+
+/// Alternating two-color strobe
+///
+/// Every flash, in either color, counts towards the flash rate. In safe mode
+/// flashes are never closer than [`MIN_SAFE_FLASH_INTERVAL`].
+#[derive(Debug, Clone)]
+pub struct StrobeEffect {
+    pattern: StrobePattern,
+    colors: [ValueTransition<Rgb>; 2],
+    style: StrobeStyle,
+}
+
+impl StrobeEffect {
+    /// Create a new red/blue strobe with the given pattern
+    pub const fn new(pattern: StrobePattern) -> Self {
+        Self {
+            pattern,
+            colors: [
+                ValueTransition::new_rgb(RED),
+                ValueTransition::new_rgb(BLUE),
+            ],
+            style: StrobeStyle {
+                interval: DEFAULT_FLASH_INTERVAL,
+                unsafe_rate: false,
+            },
+        }
+    }
+
+    /// Set the two strobe colors
+    #[must_use]
+    pub const fn with_colors(mut self, first: Rgb, second: Rgb) -> Self {
+        self.colors = [
+            ValueTransition::new_rgb(first),
+            ValueTransition::new_rgb(second),
+        ];
+        self
+    }
+
+    /// Set one of the two strobe colors, other indices are ignored
+    #[must_use]
+    pub const fn with_color_at(mut self, index: usize, color: Rgb) -> Self {
+        if index < self.colors.len() {
+            self.colors[index] = ValueTransition::new_rgb(color);
+        }
+        self
+    }
+
+    /// Set the style
+    #[must_use]
+    pub const fn with_style(mut self, style: StrobeStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the interval between flashes
+    ///
+    /// In safe mode the interval is clamped to [`MIN_SAFE_FLASH_INTERVAL`].
+    #[must_use]
+    pub fn with_flash_interval(mut self, interval: Duration) -> Self {
+        self.style.interval = interval;
+        self
+    }
+
+    /// Lift the flash rate limit
+    ///
+    /// Flashing faster than 3 Hz can trigger seizures in people with
+    /// photosensitive epilepsy. Only use this where that risk is acceptable.
+    #[must_use]
+    pub fn with_unsafe_flash_rate(mut self) -> Self {
+        self.style.unsafe_rate = true;
+        self
+    }
+
+    /// Replace the style
+    pub const fn set_style(&mut self, style: StrobeStyle) {
+        self.style = style;
+    }
+
+    /// Replace one of the two strobe colors with smooth transition
+    ///
+    /// Other indices are ignored.
+    pub fn set_color_at(
+        &mut self,
+        index: usize,
+        color: Rgb,
+        duration: Duration,
+        now: Instant,
+    ) {
+        if let Some(current) = self.colors.get_mut(index) {
+            current.set(color, duration, now);
+        }
+    }
+
+    /// Set the color space used for color transitions
+    pub fn set_blending(&mut self, blending: ColorBlending) {
+        for color in &mut self.colors {
+            color.set_blender(blending.blender());
+        }
+    }
+
+    /// Get the pattern
+    pub const fn pattern(&self) -> StrobePattern {
+        self.pattern
+    }
+
+    /// Get the effective interval between flashes
+    pub fn flash_interval(&self) -> Duration {
+        if self.style.unsafe_rate {
+            self.style.interval.max(MIN_UNSAFE_FLASH_INTERVAL)
+        } else {
+            self.style.interval.max(MIN_SAFE_FLASH_INTERVAL)
+        }
+    }
+}
+
+impl Effect for StrobeEffect {
+    const PRECISE_COLORS: bool = true;
+
+    fn render(&mut self, now: Instant, leds: &mut [Rgb]) {
+        let colors = self.colors.each_mut().map(|color| {
+            color.tick(now);
+            color.current()
+        });
+        let interval = self.flash_interval().as_millis();
+        let time_ms = now.as_millis();
+        let flash = time_ms / interval;
+        // 50% duty cycle: lit during the first half of each interval
+        let lit = time_ms % interval < interval / 2;
+
+        leds.fill(BLACK);
+        if !lit {
+            return;
+        }
+
+        let center = center_of(leds);
+        match self.pattern {
+            StrobePattern::Halves | StrobePattern::QuadFlash => {
+                let side = if self.pattern == StrobePattern::Halves {
+                    flash % 2
+                } else {
+                    (flash / QUAD_FLASHES) % 2
+                };
+                let (first, second) = leds.split_at_mut(center);
+                if side == 0 {
+                    first.fill(colors[0]);
+                } else {
+                    second.fill(colors[1]);
+                }
+            }
+            StrobePattern::Alternate => {
+                let parity = usize::from(flash % 2 == 1);
+                for led in leds.iter_mut().skip(parity).step_by(2) {
+                    *led = colors[parity];
+                }
+            }
+        }
+    }
+
+    fn is_transitioning(&self) -> bool {
+        self.colors.iter().any(ValueTransition::is_transitioning)
+    }
+}
@@ -0,0 +1,155 @@
+//! Audio-reactive VU meter
+//!
+//! Shows the RMS level as a green-to-red bar with a peak hold marker. The
+//! bar jumps up with the input and falls back smoothly.
+
+use embassy_time::Instant;
+
+use super::Effect;
+use crate::{
+    audio::AudioLevel,
+    color::{ColorStop, Rgb, sample_stops},
+    math8::scale8,
+};
+
+/// Time the peak marker stays at its position
+const PEAK_HOLD_MS: u64 = 800;
+/// Fall rate of the peak marker after the hold, in level units per second
+const PEAK_FALL_PER_SEC: u64 = 200;
+/// Fall rate of the bar, in level units per second
+const BAR_FALL_PER_SEC: u64 = 400;
+
+const GREEN: Rgb = Rgb { r: 0, g: 255, b: 0 };
+const YELLOW: Rgb = Rgb {
+    r: 255,
+    g: 200,
+    b: 0,
+};
+const RED: Rgb = Rgb { r: 255, g: 0, b: 0 };
+
+/// Meter colors along the strip
+const METER_STOPS: [ColorStop; 4] = [
+    ColorStop::new(0, GREEN),
+    ColorStop::new(150, GREEN),
+    ColorStop::new(205, YELLOW),
+    ColorStop::new(255, RED),
+];
+
+/// VU meter with peak hold
+#[derive(Debug, Clone)]
+pub struct VuMeterEffect {
+    /// Displayed bar level in 8.8 fixed-point
+    bar: u16,
+    /// Held peak level in 8.8 fixed-point
+    peak: u16,
+    peak_at: Option<Instant>,
+    last_frame: Option<Instant>,
+}
+
+impl Default for VuMeterEffect {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VuMeterEffect {
+    /// Create a new silent meter
+    pub const fn new() -> Self {
+        Self {
+            bar: 0,
+            peak: 0,
+            peak_at: None,
+            last_frame: None,
+        }
+    }
+
+    /// Feed a new audio level
+    pub fn set_level(&mut self, level: AudioLevel, now: Instant) {
+        let rms = u16::from(level.rms) << 8;
+        let peak = (u16::from(level.peak) << 8).max(rms);
+        self.bar = self.bar.max(rms);
+        if peak >= self.peak {
+            self.peak = peak;
+            self.peak_at = Some(now);
+        }
+    }
+
+    /// Get the displayed bar level (0-255)
+    pub const fn bar_level(&self) -> u8 {
+        (self.bar >> 8) as u8
+    }
+
+    /// Get the held peak level (0-255)
+    pub const fn peak_level(&self) -> u8 {
+        (self.peak >> 8) as u8
+    }
+
+    /// Let the bar and the peak marker fall
+    #[allow(clippy::cast_possible_truncation)]
+    fn decay(&mut self, now: Instant) {
+        let elapsed_ms = self
+            .last_frame
+            .map_or(0, |last| now.saturating_duration_since(last).as_millis());
+        self.last_frame = Some(now);
+
+        let bar_fall = (elapsed_ms * BAR_FALL_PER_SEC * 256 / 1_000).min(0xFFFF);
+        self.bar = self.bar.saturating_sub(bar_fall as u16);
+
+        let holding = self.peak_at.is_some_and(|peak_at| {
+            now.saturating_duration_since(peak_at).as_millis() < PEAK_HOLD_MS
+        });
+        if !holding {
+            let peak_fall =
+                (elapsed_ms * PEAK_FALL_PER_SEC * 256 / 1_000).min(0xFFFF);
+            self.peak = self.peak.saturating_sub(peak_fall as u16).max(self.bar);
+        }
+    }
+}
+
+impl Effect for VuMeterEffect {
+    const PRECISE_COLORS: bool = true;
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn render(&mut self, now: Instant, leds: &mut [Rgb]) {
+        self.decay(now);
+        leds.fill(Rgb { r: 0, g: 0, b: 0 });
+        let len = leds.len();
+        if len == 0 {
+            return;
+        }
+
+        let last = (len - 1).max(1);
+        let color_at =
+            |i: usize| sample_stops(&METER_STOPS, (i * 255 / last) as u8, false);
+        // Position of a level in 8.8 fixed-point LEDs
+        let position = |level: u16| u64::from(level) * len as u64 * 256 / 0xFF00;
+
+        // The last LED of the bar is partially lit
+        let fill = position(self.bar);
+        let full = ((fill >> 8) as usize).min(len);
+        let partial = (fill & 0xFF) as u8;
+        for (i, led) in leds.iter_mut().enumerate().take(full) {
+            *led = color_at(i);
+        }
+        if full < len && partial > 0 {
+            let color = color_at(full);
+            leds[full] = Rgb {
+                r: scale8(color.r, partial),
+                g: scale8(color.g, partial),
+                b: scale8(color.b, partial),
+            };
+        }
+
+        if self.peak > 0 {
+            // LED holding the top of the peak level
+            let peak = (position(self.peak).div_ceil(256) as usize)
+                .saturating_sub(1)
+                .min(len - 1);
+            leds[peak] = color_at(peak);
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
@@ -11,10 +11,10 @@ use embassy_time::{Duration, Instant};
 #[cfg(feature = "esp32-log")]
 use esp_println::println;
 
-use super::Filter;
+use super::{Filter, map_levels, widen};
 use crate::{
-    color::Rgb,
-    math8::{U8Adjuster, scale8},
+    color::Rgb16,
+    math8::{U8Adjuster, scale8, scale16},
     transition::ValueTransition,
 };
 
@@ -60,8 +60,9 @@ pub(crate) struct BrightnessFilter {
     min_brightness: u8,
     scale: u8,
     adjust: Option<U8Adjuster>,
-    /// Current brightness value (0-255)
-    brightness: ValueTransition<u8>,
+    /// Current brightness value in 16 bits (0-65535), fades keep the
+    /// fractional part between 8-bit levels
+    brightness: ValueTransition<u16>,
 }
 
 impl BrightnessFilter {
@@ -74,7 +75,7 @@ impl BrightnessFilter {
             min_brightness: config.min_brightness,
             scale: config.scale,
             adjust: config.adjust,
-            brightness: ValueTransition::new_u8(brightness),
+            brightness: ValueTransition::new_u16(widen(brightness)),
         }
     }
 
@@ -88,7 +89,8 @@ impl BrightnessFilter {
             "[BrightnessFilter.set] setting brightness to {:?} ({:?})",
             brightness, corrected_brightness
         );
-        self.brightness.set(corrected_brightness, duration, now);
+        self.brightness
+            .set(widen(corrected_brightness), duration, now);
     }
 
     pub(crate) fn set_uncorrected(
@@ -97,7 +99,7 @@ impl BrightnessFilter {
         duration: Duration,
         now: Instant,
     ) {
-        self.brightness.set(brightness, duration, now);
+        self.brightness.set(widen(brightness), duration, now);
     }
 
     /// Check if a transition is in progress
@@ -116,31 +118,38 @@ impl BrightnessFilter {
     pub(crate) fn set_adjuster(&mut self, adjust: Option<U8Adjuster>) {
         self.adjust = adjust;
     }
-}
-
-impl Filter for BrightnessFilter {
-    fn apply(&mut self, frame: &mut [Rgb]) {
-        let mut current = self.brightness.current();
 
-        if current == 255 {
-            return;
-        }
+    /// Check if the brightness sits exactly on an 8-bit step
+    ///
+    /// Frames on a step need no dithering, so a steady brightness is shown
+    /// without flicker.
+    pub(crate) fn is_on_step(&self) -> bool {
+        self.current().is_multiple_of(257)
+    }
 
-        if current == 0 {
-            for pixel in frame.iter_mut() {
-                *pixel = Rgb { r: 0, g: 0, b: 0 };
+    /// Get the current brightness in 16 bits, with the adjuster applied
+    fn current(&self) -> u16 {
+        let current = self.brightness.current();
+        match self.adjust {
+            Some(adjust) if current != 0 && current != u16::MAX => {
+                map_levels(current, adjust)
             }
-            return;
+            _ => current,
         }
+    }
+}
 
-        if let Some(adjust) = self.adjust {
-            current = adjust(current);
+impl Filter for BrightnessFilter {
+    fn apply(&mut self, frame: &mut [Rgb16]) {
+        let brightness = self.current();
+        if brightness == u16::MAX {
+            return;
         }
 
         for pixel in frame.iter_mut() {
-            pixel.r = scale8(pixel.r, current);
-            pixel.g = scale8(pixel.g, current);
-            pixel.b = scale8(pixel.b, current);
+            pixel.r = scale16(pixel.r, brightness);
+            pixel.g = scale16(pixel.g, brightness);
+            pixel.b = scale16(pixel.b, brightness);
         }
     }
 
@@ -2,8 +2,11 @@
 //!
 //! Applies per-channel multiplicative scaling to correct color output.
 
-use super::Filter;
-use crate::{color::Rgb, math8::scale8};
+use super::{Filter, widen};
+use crate::{
+    color::{Rgb, Rgb16},
+    math8::scale16,
+};
 
 /// Color correction filter
 ///
@@ -27,16 +30,20 @@ impl ColorCorrection {
 }
 
 impl Filter for ColorCorrection {
-    fn apply(&mut self, frame: &mut [Rgb]) {
+    fn apply(&mut self, frame: &mut [Rgb16]) {
         if !self.is_active() {
             return;
         }
 
-        // TODO: add gamma correction?
+        let (r, g, b) = (
+            widen(self.factors.r),
+            widen(self.factors.g),
+            widen(self.factors.b),
+        );
         for pixel in frame.iter_mut() {
-            pixel.r = scale8(pixel.r, self.factors.r);
-            pixel.g = scale8(pixel.g, self.factors.g);
-            pixel.b = scale8(pixel.b, self.factors.b);
+            pixel.r = scale16(pixel.r, r);
+            pixel.g = scale16(pixel.g, g);
+            pixel.b = scale16(pixel.b, b);
         }
     }
 }
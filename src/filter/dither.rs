@@ -0,0 +1,38 @@
+//! Temporal dithering
+//!
+//! Rounds 16-bit channel values to 8 bits with a threshold that changes from
+//! frame to frame, so values between two 8-bit levels show as their average
+//! over time instead of snapping to one of them.
+
+/// Frame-to-frame dither state
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TemporalDither {
+    frame: u8,
+}
+
+impl TemporalDither {
+    /// Create a new dither state
+    pub(crate) const fn new() -> Self {
+        Self { frame: 0 }
+    }
+
+    /// Move on to the next frame
+    pub(crate) const fn advance(&mut self) {
+        self.frame = self.frame.wrapping_add(1);
+    }
+
+    /// Round a 16-bit value to 8 bits for the LED at `index`
+    ///
+    /// The threshold runs through all 256 levels in bit-reversed order, so
+    /// neighboring frames differ the most. LEDs are offset from each other
+    /// to keep them from flickering in sync.
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) const fn dither(&self, value: u16, index: usize) -> u8 {
+        let threshold = self
+            .frame
+            .reverse_bits()
+            .wrapping_add((index as u8).wrapping_mul(97));
+        let value = (value as u32 + threshold as u32) >> 8;
+        if value > 255 { 255 } else { value as u8 }
+    }
+}
@@ -0,0 +1,46 @@
+//! Gamma correction filter
+//!
+//! Maps rendered values through per-channel gamma tables.
+
+use super::{Filter, map_levels};
+use crate::{color::Rgb16, gamma::Gamma};
+
+/// Gamma correction filter
+///
+/// Holds the configured tables and whether they are currently applied.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct GammaCorrection {
+    /// Tables set at configuration time
+    gamma: Option<&'static Gamma>,
+    /// Whether the tables are applied
+    enabled: bool,
+}
+
+impl GammaCorrection {
+    /// Create a new gamma correction, `None` leaves values linear
+    pub(crate) const fn new(gamma: Option<&'static Gamma>) -> Self {
+        Self {
+            gamma,
+            enabled: true,
+        }
+    }
+
+    /// Switch the correction on or off
+    pub(crate) const fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+}
+
+impl Filter for GammaCorrection {
+    fn apply(&mut self, frame: &mut [Rgb16]) {
+        let Some(gamma) = self.gamma.filter(|_| self.enabled) else {
+            return;
+        };
+
+        for pixel in frame.iter_mut() {
+            pixel.r = map_levels(pixel.r, |value| gamma.r.apply(value));
+            pixel.g = map_levels(pixel.g, |value| gamma.g.apply(value));
+            pixel.b = map_levels(pixel.b, |value| gamma.b.apply(value));
+        }
+    }
+}
@@ -1,13 +1,19 @@
 use embassy_time::Instant;
 
-use crate::color::Rgb;
+use crate::{
+    color::{Rgb, Rgb16},
+    gamma::Gamma,
+    math8::blend16,
+};
 
 mod brightness;
 mod color_correction;
+mod dither;
+mod gamma;
 
 pub(crate) trait Filter {
-    /// Apply the effect to a frame
-    fn apply(&mut self, frame: &mut [Rgb]);
+    /// Apply the effect to a 16-bit frame
+    fn apply(&mut self, frame: &mut [Rgb16]);
 
     fn tick(&mut self, _now: Instant) {}
 }
@@ -15,6 +21,63 @@ pub(crate) trait Filter {
 use brightness::BrightnessFilter;
 pub use brightness::{BrightnessFilterConfig, BrightnessRange};
 pub(crate) use color_correction::ColorCorrection;
+pub(crate) use dither::TemporalDither;
+pub(crate) use gamma::GammaCorrection;
+
+/// Widen an 8-bit value to 16 bits (255 = 65535)
+pub(crate) const fn widen(value: u8) -> u16 {
+    value as u16 * 257
+}
+
+/// Map a 16-bit value through an 8-bit lookup
+///
+/// Values between two 8-bit levels are interpolated between the mapped
+/// levels, values on a level map exactly.
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn map_levels(value: u16, map: impl Fn(u8) -> u8) -> u16 {
+    let level = (value / 257) as u8;
+    let fraction = value - widen(level);
+    let low = widen(map(level));
+    if fraction == 0 {
+        return low;
+    }
+    let high = widen(map(level.saturating_add(1)));
+    blend16(low, high, (u32::from(fraction) * 255 / 257) as u8)
+}
+
+/// Copy an 8-bit frame into a 16-bit one
+pub(crate) fn widen_frame(frame: &[Rgb], wide: &mut [Rgb16]) {
+    for (pixel, wide) in frame.iter().zip(wide.iter_mut()) {
+        *wide = Rgb16 {
+            r: widen(pixel.r),
+            g: widen(pixel.g),
+            b: widen(pixel.b),
+        };
+    }
+}
+
+/// Round a 16-bit frame down to 8 bits, with `dither` if set
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn narrow_frame(
+    wide: &[Rgb16],
+    frame: &mut [Rgb],
+    dither: Option<&TemporalDither>,
+) {
+    for (i, (wide, pixel)) in wide.iter().zip(frame.iter_mut()).enumerate() {
+        *pixel = match dither {
+            Some(dither) => Rgb {
+                r: dither.dither(wide.r, i),
+                g: dither.dither(wide.g, i),
+                b: dither.dither(wide.b, i),
+            },
+            None => Rgb {
+                r: (wide.r >> 8) as u8,
+                g: (wide.g >> 8) as u8,
+                b: (wide.b >> 8) as u8,
+            },
+        };
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct FilterProcessorConfig {
@@ -22,6 +85,11 @@ pub struct FilterProcessorConfig {
     pub brightness: BrightnessFilterConfig,
     /// Color correction
     pub color_correction: Rgb,
+    /// Gamma tables, `None` leaves values linear
+    ///
+    /// The correction can be switched off and on at runtime with
+    /// [`LightChangeIntent::Gamma`](crate::LightChangeIntent::Gamma).
+    pub gamma: Option<&'static Gamma>,
 }
 
 /// Filter processor - applies post-processing to frames
@@ -34,6 +102,8 @@ pub(crate) struct FilterProcessor {
     pub brightness: BrightnessFilter,
     /// Color correction filter
     pub color_correction: ColorCorrection,
+    /// Gamma correction filter
+    pub gamma: GammaCorrection,
 }
 
 impl FilterProcessor {
@@ -41,9 +111,11 @@ impl FilterProcessor {
     pub(crate) fn new(config: &FilterProcessorConfig) -> Self {
         let brightness = BrightnessFilter::new(0, &config.brightness);
         let color_correction = ColorCorrection::new(config.color_correction);
+        let gamma = GammaCorrection::new(config.gamma);
         Self {
             brightness,
             color_correction,
+            gamma,
         }
     }
 
@@ -51,5 +123,6 @@ impl FilterProcessor {
     pub(crate) fn tick(&mut self, now: Instant) {
         self.brightness.tick(now);
         self.color_correction.tick(now);
+        self.gamma.tick(now);
     }
 }
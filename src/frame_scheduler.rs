@@ -108,8 +108,10 @@ impl<'a, O: OutputDriver, const MAX_LEDS: usize, const INTENT_CHANNEL_SIZE: usiz
         }
 
         // Render and output
-        let frame = self.renderer.render(now);
-        self.output.write(frame);
+        self.renderer.render(now);
+        self.output
+            .set_color_temperature(self.renderer.color_temperature());
+        self.output.write(self.renderer.frame());
 
         // Calculate next frame deadline
         self.next_frame += self.frame_duration;
@@ -132,6 +134,11 @@ impl<'a, O: OutputDriver, const MAX_LEDS: usize, const INTENT_CHANNEL_SIZE: usiz
         &self.renderer
     }
 
+    /// Get a reference to the output driver.
+    pub fn output(&self) -> &O {
+        &self.output
+    }
+
     /// Get a mutable reference to the renderer.
     pub fn renderer_mut(
         &mut self,
@@ -1,3 +1,13 @@
+//! Gamma correction
+//!
+//! LEDs emit light linearly to their PWM duty, while the eye perceives it
+//! roughly as a power curve. Gamma tables map the rendered values to duty
+//! values so that gradients and fades look even. Tables are generated in
+//! const context, so a `const` or `static` table costs no RAM or startup
+//! time.
+
+use crate::{color::Rgb, math8::const_powf};
+
 // Pre-computed 256-entry lookup table for gamma 2.2 for WS2812B
 const GAMMA_22_WS2812: [u8; 256] = [
     0, 0, 0, 1, 1, 1, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9,
@@ -21,3 +31,97 @@ const GAMMA_22_WS2812: [u8; 256] = [
 pub fn ws2812_lut(value: u8) -> u8 {
     GAMMA_22_WS2812[value as usize]
 }
+
+/// Gamma lookup table for one channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GammaTable([u8; 256]);
+
+impl GammaTable {
+    /// Table that leaves values unchanged
+    pub const LINEAR: Self = Self::new(1.0);
+
+    /// Pre-computed table for WS2812B, see [`ws2812_lut`]
+    pub const WS2812: Self = Self(GAMMA_22_WS2812);
+
+    /// Generate a table for the given gamma
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `gamma` is finite and in `(0, 10]`.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub const fn new(gamma: f32) -> Self {
+        assert!(
+            gamma.is_finite() && gamma > 0.0 && gamma <= 10.0,
+            "gamma must be in (0, 10]"
+        );
+
+        let mut table = [0; 256];
+        let mut i = 1;
+        while i < 256 {
+            let normalized = i as f64 / 255.0;
+            let corrected = const_powf(normalized, gamma as f64);
+            table[i] = (corrected * 255.0 + 0.5) as u8;
+            i += 1;
+        }
+        Self(table)
+    }
+
+    /// Look up the corrected value
+    #[inline]
+    pub const fn apply(&self, value: u8) -> u8 {
+        self.0[value as usize]
+    }
+}
+
+/// Gamma correction with a table per channel
+///
+/// Strips often need a slightly different curve per channel to keep whites
+/// neutral over the whole brightness range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gamma {
+    pub r: GammaTable,
+    pub g: GammaTable,
+    pub b: GammaTable,
+}
+
+impl Gamma {
+    /// Pre-computed WS2812B tables on all channels
+    pub const WS2812: Self = Self::from_table(GammaTable::WS2812);
+
+    /// Generate tables with a gamma per channel
+    pub const fn new(r: f32, g: f32, b: f32) -> Self {
+        Self {
+            r: GammaTable::new(r),
+            g: GammaTable::new(g),
+            b: GammaTable::new(b),
+        }
+    }
+
+    /// Generate tables with the same gamma on all channels
+    pub const fn uniform(gamma: f32) -> Self {
+        Self::from_table(GammaTable::new(gamma))
+    }
+
+    /// Use the same table on all channels
+    pub const fn from_table(table: GammaTable) -> Self {
+        Self {
+            r: table,
+            g: table,
+            b: table,
+        }
+    }
+
+    /// Correct a color
+    #[inline]
+    pub const fn apply(&self, color: Rgb) -> Rgb {
+        Rgb {
+            r: self.r.apply(color.r),
+            g: self.g.apply(color.g),
+            b: self.b.apply(color.b),
+        }
+    }
+}
@@ -4,12 +4,27 @@
 
 use crate::{
     U8Adjuster,
+    beat::BeatSync,
     bounds::RenderingBounds,
     channel::{Channel, Receiver, Sender},
-    color::{Rgb, kelvin_to_rgb},
-    effect::EffectId,
+    color::{ColorSlot, Rgb},
+    effect::{
+        CircadianCurve,
+        ClockStyle,
+        EffectId,
+        FlowPalette,
+        GarlandColors,
+        GarlandStyle,
+        GaugeStyle,
+        GradientConfig,
+        RainStyle,
+        StrobeStyle,
+    },
     filter::BrightnessRange,
     operation::OperationStack,
+    overlay::Overlay,
+    time_of_day::TimeOfDay,
+    transition::ColorBlending,
 };
 
 /// Represents a user intent to change the light state.
@@ -18,8 +33,27 @@ pub struct LightStateIntent {
     pub power: Option<bool>,
     pub brightness: Option<u8>,
     pub color: Option<Rgb>,
+    pub secondary_color: Option<Rgb>,
+    pub tertiary_color: Option<Rgb>,
     pub color_temperature: Option<u16>,
     pub effect_id: Option<EffectId>,
+    /// Colors of the garland effect, replacing the ones from the color slots
+    pub garland_colors: Option<GarlandColors>,
+    /// Color space of the color transitions, `None` uses the configured one
+    pub color_blending: Option<ColorBlending>,
+}
+
+impl LightStateIntent {
+    /// Get the color requested for a slot
+    ///
+    /// The primary slot is the `color` field.
+    pub const fn slot_color(&self, slot: ColorSlot) -> Option<Rgb> {
+        match slot {
+            ColorSlot::Primary => self.color,
+            ColorSlot::Secondary => self.secondary_color,
+            ColorSlot::Tertiary => self.tertiary_color,
+        }
+    }
 }
 
 /// Intent to change light state or settings
@@ -35,6 +69,36 @@ pub enum LightChangeIntent {
     BrightnessRange(BrightnessRange),
     /// Change the brightness adjuster
     Adjuster(Option<U8Adjuster>),
+    /// Report the current wall-clock time of day
+    TimeOfDay(TimeOfDay),
+    /// Change the daily curve of the circadian effect
+    CircadianCurve(CircadianCurve),
+    /// Change the tempo of beat-driven effects (beats per minute)
+    Bpm(u16),
+    /// Change the level shown by the gauge effect (0-65535)
+    Level(u16),
+    /// Change the style of the gauge effect
+    GaugeStyle(GaugeStyle),
+    /// Change the style and ring layout of the clock effect
+    ClockStyle(ClockStyle),
+    /// Change the density and speed of the rain effect
+    RainStyle(RainStyle),
+    /// Change the flash rate of the strobe effects
+    StrobeStyle(StrobeStyle),
+    /// Change the color stops of the gradient effect
+    Gradient(GradientConfig),
+    /// Change the palette of flow effects, `None` restores the built-in one
+    FlowPalette(Option<FlowPalette>),
+    /// Change the layout and animation of the garland effect
+    GarlandStyle(GarlandStyle),
+    /// Show a notification on top of the current effect, `None` cancels it
+    Overlay(Option<Overlay>),
+    /// Change how the light follows the beat
+    BeatSync(BeatSync),
+    /// Change the color space of color transitions
+    ColorBlending(ColorBlending),
+    /// Switch the configured gamma correction on or off
+    Gamma(bool),
 }
 
 /// Side effects from processing intents that the renderer should apply
@@ -48,6 +112,38 @@ pub struct IntentEffects {
     pub brightness_range: Option<BrightnessRange>,
     /// New brightness adjuster to apply
     pub adjuster: Option<Option<U8Adjuster>>,
+    /// Time of day reported by the caller
+    pub time_of_day: Option<TimeOfDay>,
+    /// New circadian curve to apply
+    pub circadian_curve: Option<CircadianCurve>,
+    /// New tempo to apply (beats per minute)
+    pub bpm: Option<u16>,
+    /// New gauge level to apply
+    pub level: Option<u16>,
+    /// New gauge style to apply
+    pub gauge_style: Option<GaugeStyle>,
+    /// New clock style to apply
+    pub clock_style: Option<ClockStyle>,
+    /// New rain style to apply
+    pub rain_style: Option<RainStyle>,
+    /// New strobe style to apply
+    pub strobe_style: Option<StrobeStyle>,
+    /// New gradient to apply
+    pub gradient: Option<GradientConfig>,
+    /// New flow palette to apply
+    pub flow_palette: Option<Option<FlowPalette>>,
+    /// New garland colors to apply
+    pub garland_colors: Option<GarlandColors>,
+    /// New garland style to apply
+    pub garland_style: Option<GarlandStyle>,
+    /// New overlay to show or cancel
+    pub overlay: Option<Option<Overlay>>,
+    /// New beat sync settings to apply
+    pub beat_sync: Option<BeatSync>,
+    /// New color space of color transitions
+    pub color_blending: Option<ColorBlending>,
+    /// Whether to apply the gamma correction
+    pub gamma: Option<bool>,
 }
 
 impl IntentEffects {
@@ -57,6 +153,22 @@ impl IntentEffects {
             || self.color_correction.is_some()
             || self.brightness_range.is_some()
             || self.adjuster.is_some()
+            || self.time_of_day.is_some()
+            || self.circadian_curve.is_some()
+            || self.bpm.is_some()
+            || self.level.is_some()
+            || self.gauge_style.is_some()
+            || self.clock_style.is_some()
+            || self.rain_style.is_some()
+            || self.strobe_style.is_some()
+            || self.gradient.is_some()
+            || self.flow_palette.is_some()
+            || self.garland_colors.is_some()
+            || self.garland_style.is_some()
+            || self.overlay.is_some()
+            || self.beat_sync.is_some()
+            || self.color_blending.is_some()
+            || self.gamma.is_some()
     }
 }
 
@@ -100,6 +212,9 @@ impl<'a, const SIZE: usize> IntentProcessor<'a, SIZE> {
                         &state_intent,
                         current_brightness,
                     );
+                    if let Some(colors) = state_intent.garland_colors {
+                        effects.garland_colors = Some(colors);
+                    }
                 }
                 LightChangeIntent::Bounds(bounds) => {
                     effects.bounds = Some(bounds);
@@ -113,6 +228,51 @@ impl<'a, const SIZE: usize> IntentProcessor<'a, SIZE> {
                 LightChangeIntent::Adjuster(adjuster) => {
                     effects.adjuster = Some(adjuster);
                 }
+                LightChangeIntent::TimeOfDay(time_of_day) => {
+                    effects.time_of_day = Some(time_of_day);
+                }
+                LightChangeIntent::CircadianCurve(curve) => {
+                    effects.circadian_curve = Some(curve);
+                }
+                LightChangeIntent::Bpm(bpm) => {
+                    effects.bpm = Some(bpm);
+                }
+                LightChangeIntent::Level(level) => {
+                    effects.level = Some(level);
+                }
+                LightChangeIntent::GaugeStyle(style) => {
+                    effects.gauge_style = Some(style);
+                }
+                LightChangeIntent::ClockStyle(style) => {
+                    effects.clock_style = Some(style);
+                }
+                LightChangeIntent::RainStyle(style) => {
+                    effects.rain_style = Some(style);
+                }
+                LightChangeIntent::StrobeStyle(style) => {
+                    effects.strobe_style = Some(style);
+                }
+                LightChangeIntent::Gradient(config) => {
+                    effects.gradient = Some(config);
+                }
+                LightChangeIntent::FlowPalette(palette) => {
+                    effects.flow_palette = Some(palette);
+                }
+                LightChangeIntent::GarlandStyle(style) => {
+                    effects.garland_style = Some(style);
+                }
+                LightChangeIntent::Overlay(overlay) => {
+                    effects.overlay = Some(overlay);
+                }
+                LightChangeIntent::BeatSync(sync) => {
+                    effects.beat_sync = Some(sync);
+                }
+                LightChangeIntent::ColorBlending(blending) => {
+                    effects.color_blending = Some(blending);
+                }
+                LightChangeIntent::Gamma(enabled) => {
+                    effects.gamma = Some(enabled);
+                }
             }
         }
 
@@ -134,10 +294,19 @@ impl<'a, const SIZE: usize> IntentProcessor<'a, SIZE> {
         }
 
         if let Some(color) = intent.color {
-            let _ = stack.push_color(color);
-        } else if let Some(temp_kelvin) = intent.color_temperature {
-            let color = kelvin_to_rgb(temp_kelvin);
-            let _ = stack.push_color(color);
+            let _ = stack.push_slot_color(
+                ColorSlot::Primary,
+                color,
+                intent.color_blending,
+            );
+        } else if let Some(kelvin) = intent.color_temperature {
+            let _ = stack.push_color_temperature(kelvin, intent.color_blending);
+        }
+
+        for slot in [ColorSlot::Secondary, ColorSlot::Tertiary] {
+            if let Some(color) = intent.slot_color(slot) {
+                let _ = stack.push_slot_color(slot, color, intent.color_blending);
+            }
         }
 
         if let Some(power) = intent.power {
@@ -1,5 +1,7 @@
 #![no_std]
 
+pub mod audio;
+pub mod beat;
 pub mod bounds;
 pub mod channel;
 pub mod color;
@@ -10,15 +12,20 @@ pub mod gamma;
 pub mod intent_processor;
 pub mod math8;
 pub mod operation;
+pub mod output;
+pub mod overlay;
 pub mod renderer;
+pub mod time_of_day;
 pub mod transition;
 
-pub use color::{Hsv, Rgb};
+pub use audio::{AudioFeed, AudioInput, AudioLevel, Spectrum};
+pub use beat::{Beat, BeatDetector, BeatSync, BeatTracker};
+pub use color::{ColorOrder, Hsv, Rgb, Rgbcct, Rgbw};
 pub use effect::{EffectId, EffectSlot};
 pub use embassy_time::{Duration, Instant};
 pub use filter::{BrightnessRange, FilterProcessorConfig};
 pub use frame_scheduler::FrameScheduler;
-pub use gamma::ws2812_lut;
+pub use gamma::{Gamma, GammaTable, ws2812_lut};
 pub use intent_processor::{
     IntentChannel,
     IntentEffects,
@@ -30,7 +37,11 @@ pub use intent_processor::{
 };
 pub use math8::{U8Adjuster, ease_in_out_quad};
 pub use operation::{Operation, OperationStack};
+pub use output::{ColorTemperature, RgbOutput, RgbcctOutput, RgbwOutput};
+pub use overlay::{Overlay, OverlayLength, OverlayPattern};
 pub use renderer::{LightEngineConfig, LightState, Renderer, TransitionTimings};
+pub use time_of_day::{DayClock, TimeOfDay};
+pub use transition::ColorBlending;
 
 /// Abstract LED driver trait
 ///
@@ -39,4 +50,26 @@ pub use renderer::{LightEngineConfig, LightState, Renderer, TransitionTimings};
 pub trait OutputDriver {
     /// Write colors to the LED strip
     fn write(&mut self, colors: &[Rgb]);
+
+    /// Receive the color temperature shown by white channels
+    ///
+    /// Called before every [`Self::write`], `None` while only RGB is used.
+    /// Only outputs with white channels need it.
+    fn set_color_temperature(&mut self, _temperature: Option<ColorTemperature>) {}
+}
+
+/// Abstract driver trait for RGBW strips
+///
+/// Wrap it in [`RgbwOutput`] to use it as an [`OutputDriver`].
+pub trait RgbwOutputDriver {
+    /// Write colors with a white channel to the LED strip
+    fn write_rgbw(&mut self, colors: &[Rgbw]);
+}
+
+/// Abstract driver trait for RGB strips with warm and cool white channels
+///
+/// Wrap it in [`RgbcctOutput`] to use it as an [`OutputDriver`].
+pub trait RgbcctOutputDriver {
+    /// Write colors with two white channels to the LED strip
+    fn write_rgbcct(&mut self, colors: &[Rgbcct]);
 }
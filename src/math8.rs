@@ -9,6 +9,13 @@ pub const fn scale8(value: u8, scale: u8) -> u8 {
     ((value as u16 * (1 + scale as u16)) >> 8) as u8
 }
 
+/// Scale a 16-bit value by a 16-bit factor (0-65535 = 0.0-1.0)
+#[inline]
+#[allow(clippy::cast_possible_truncation)]
+pub const fn scale16(value: u16, scale: u16) -> u16 {
+    ((value as u32 * (scale as u32 + 1)) >> 16) as u16
+}
+
 /// Blend two 8-bit values
 #[inline]
 #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
@@ -26,6 +33,15 @@ pub const fn blend8(a: u8, b: u8, amount_of_b: u8) -> u8 {
     (partial >> 16) as u8
 }
 
+/// Blend two 16-bit values
+#[inline]
+#[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+pub const fn blend16(a: u16, b: u16, amount_of_b: u8) -> u16 {
+    let delta = b as i32 - a as i32;
+    let scaled = delta * amount_of_b as i32 / 255;
+    (a as i32 + scaled) as u16
+}
+
 /// Calculate progress (0-255) based on elapsed time and duration
 #[allow(clippy::cast_possible_truncation)]
 #[inline]
@@ -55,3 +71,118 @@ pub fn ease_in_out_quad(i: u8) -> u8 {
     let jj2 = jj << 1;
     if i & 0x80 == 0 { jj2 } else { 255 - jj2 }
 }
+
+/// Slope/offset pairs for the four quarter-wave sections used by [`sin8`]
+const SIN8_SECTIONS: [u8; 8] = [0, 49, 49, 41, 90, 27, 117, 10];
+
+/// Fast 8-bit sine approximation (ported from `FastLED`)
+///
+/// The input angle is a full circle on 0-255 and the output is on 0-255,
+/// centered at 128: `sin8(0) == 128`, `sin8(64) == 255`.
+#[inline]
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_sign_loss
+)]
+pub const fn sin8(theta: u8) -> u8 {
+    let mut offset = theta;
+    if theta & 0x40 != 0 {
+        offset = 255 - offset;
+    }
+    offset &= 0x3F;
+
+    let mut sec_offset = offset & 0x0F;
+    if theta & 0x40 != 0 {
+        sec_offset += 1;
+    }
+
+    let section = (offset >> 4) as usize;
+    let b = SIN8_SECTIONS[section * 2];
+    let m16 = SIN8_SECTIONS[section * 2 + 1];
+    let mx = ((m16 as u16 * sec_offset as u16) >> 4) as u8;
+
+    let mut y = mx.wrapping_add(b) as i8;
+    if theta & 0x80 != 0 {
+        y = y.wrapping_neg();
+    }
+    (y as u8).wrapping_add(128)
+}
+
+/// Fast 8-bit cosine approximation, see [`sin8`]
+#[inline]
+pub const fn cos8(theta: u8) -> u8 {
+    sin8(theta.wrapping_add(64))
+}
+
+// Float math for generating lookup tables in const context. Tables are
+// computed by the compiler, so targets without an FPU only read them.
+
+/// Natural logarithm of 2
+const LN_2: f64 = core::f64::consts::LN_2;
+
+/// Natural logarithm of `x` > 0
+pub(crate) const fn const_ln(x: f64) -> f64 {
+    // Scale into [1, 2), where the series below converges quickly
+    let mut x = x;
+    let mut exponent = 0.0;
+    while x < 1.0 {
+        x *= 2.0;
+        exponent -= 1.0;
+    }
+    while x >= 2.0 {
+        x /= 2.0;
+        exponent += 1.0;
+    }
+
+    // ln(x) = 2 * atanh((x - 1) / (x + 1))
+    let z = (x - 1.0) / (x + 1.0);
+    let z2 = z * z;
+    let mut term = z;
+    let mut sum = 0.0;
+    let mut i = 0;
+    while i < 20 {
+        sum += term / (2 * i + 1) as f64;
+        term *= z2;
+        i += 1;
+    }
+    2.0 * sum + exponent * LN_2
+}
+
+/// Exponential of `y`
+pub(crate) const fn const_exp(y: f64) -> f64 {
+    // Scale into (-ln 2, 0], where the Taylor series converges quickly
+    let mut y = y;
+    let mut exponent = 0;
+    while y > 0.0 {
+        y -= LN_2;
+        exponent += 1;
+    }
+    while y < -LN_2 {
+        y += LN_2;
+        exponent -= 1;
+    }
+
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    let mut i = 1;
+    while i < 20 {
+        term *= y / i as f64;
+        sum += term;
+        i += 1;
+    }
+    while exponent > 0 {
+        sum *= 2.0;
+        exponent -= 1;
+    }
+    while exponent < 0 {
+        sum /= 2.0;
+        exponent += 1;
+    }
+    sum
+}
+
+/// `x` > 0 raised to the power of `exponent`
+pub(crate) const fn const_powf(x: f64, exponent: f64) -> f64 {
+    const_exp(exponent * const_ln(x))
+}
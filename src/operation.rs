@@ -1,6 +1,10 @@
 use heapless::Deque;
 
-use crate::{color::Rgb, effect::EffectId};
+use crate::{
+    color::{ColorSlot, Rgb},
+    effect::EffectId,
+    transition::ColorBlending,
+};
 
 /// Operations that can be performed on the light engine
 ///
@@ -11,8 +15,15 @@ pub enum Operation {
     SetBrightness(u8),
     /// Switch to a new effect with fade transition
     SwitchEffect(EffectId),
-    /// Update effect color
-    SetColor(Rgb),
+    /// Update effect color in a slot
+    ///
+    /// The color transition blends in the given color space, `None` uses the
+    /// configured one.
+    SetColor(ColorSlot, Rgb, Option<ColorBlending>),
+    /// Update the primary color to a color temperature in Kelvin
+    ///
+    /// Blends like [`Operation::SetColor`].
+    SetColorTemperature(u16, Option<ColorBlending>),
     /// Power off the light (fade out to 0, but preserve target brightness).
     PowerOff,
     /// Power on the light (fade in from 0 to the stored target brightness).
@@ -67,9 +78,31 @@ impl<const N: usize> OperationStack<N> {
         self.push(Operation::SetBrightness(brightness))
     }
 
-    /// Push a color operation onto the stack
+    /// Push a primary color operation onto the stack
     pub fn push_color(&mut self, color: Rgb) -> Result<(), Operation> {
-        self.push(Operation::SetColor(color))
+        self.push_slot_color(ColorSlot::Primary, color, None)
+    }
+
+    /// Push a color operation for the given slot onto the stack
+    ///
+    /// `blending` selects the color space of the transition, `None` uses the
+    /// configured one.
+    pub fn push_slot_color(
+        &mut self,
+        slot: ColorSlot,
+        color: Rgb,
+        blending: Option<ColorBlending>,
+    ) -> Result<(), Operation> {
+        self.push(Operation::SetColor(slot, color, blending))
+    }
+
+    /// Push a color temperature operation onto the stack
+    pub fn push_color_temperature(
+        &mut self,
+        kelvin: u16,
+        blending: Option<ColorBlending>,
+    ) -> Result<(), Operation> {
+        self.push(Operation::SetColorTemperature(kelvin, blending))
     }
 
     /// Push a effect operation onto the stack
@@ -0,0 +1,221 @@
+//! Output adapters for strips with other channel layouts
+
+use crate::{
+    OutputDriver,
+    RgbcctOutputDriver,
+    RgbwOutputDriver,
+    color::{
+        CctConfig,
+        CctWhiteOrder,
+        ColorOrder,
+        Rgb,
+        Rgbcct,
+        Rgbw,
+        WhiteExtraction,
+        WhitePosition,
+        blend_colors,
+        rgbcct,
+        rgbw,
+    },
+    math8::blend8,
+};
+
+/// Color temperature shown by the white channels of a strip
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColorTemperature {
+    /// Color temperature in Kelvin
+    pub kelvin: u16,
+    /// Share of the light shown by the white channels (0-255)
+    ///
+    /// Ramps up and down while crossfading between RGB and the whites.
+    pub amount: u8,
+}
+
+/// Output adapter putting RGB frames in the channel order of the strip
+///
+/// N is the maximum number of LEDs in the strip.
+pub struct RgbOutput<D: OutputDriver, const N: usize> {
+    driver: D,
+    order: ColorOrder,
+    frame: [Rgb; N],
+}
+
+impl<D: OutputDriver, const N: usize> RgbOutput<D, N> {
+    /// Create a new adapter
+    pub const fn new(driver: D, order: ColorOrder) -> Self {
+        Self {
+            driver,
+            order,
+            frame: [Rgb { r: 0, g: 0, b: 0 }; N],
+        }
+    }
+
+    /// Change the channel order of the strip
+    pub fn set_order(&mut self, order: ColorOrder) {
+        self.order = order;
+    }
+
+    /// Get a reference to the driver
+    pub fn driver(&self) -> &D {
+        &self.driver
+    }
+}
+
+impl<D: OutputDriver, const N: usize> OutputDriver for RgbOutput<D, N> {
+    fn write(&mut self, colors: &[Rgb]) {
+        let len = colors.len().min(N);
+        for (pixel, color) in self.frame.iter_mut().zip(colors) {
+            *pixel = self.order.apply(*color);
+        }
+        self.driver.write(&self.frame[..len]);
+    }
+
+    fn set_color_temperature(&mut self, temperature: Option<ColorTemperature>) {
+        self.driver.set_color_temperature(temperature);
+    }
+}
+
+/// Output adapter feeding RGB frames to an RGBW driver
+///
+/// Splits every color into RGB and W channels with a [`WhiteExtraction`],
+/// then puts the channels in the order of the strip. While the light shows a
+/// color temperature, the W channel carries its level. N is the maximum
+/// number of LEDs in the strip.
+pub struct RgbwOutput<D: RgbwOutputDriver, const N: usize> {
+    driver: D,
+    extraction: WhiteExtraction,
+    order: ColorOrder,
+    white: WhitePosition,
+    temperature: Option<ColorTemperature>,
+    frame: [Rgbw; N],
+}
+
+impl<D: RgbwOutputDriver, const N: usize> RgbwOutput<D, N> {
+    /// Create a new adapter
+    pub const fn new(driver: D, extraction: WhiteExtraction) -> Self {
+        Self {
+            driver,
+            extraction,
+            order: ColorOrder::Rgb,
+            white: WhitePosition::Last,
+            temperature: None,
+            frame: [rgbw(Rgb { r: 0, g: 0, b: 0 }, 0); N],
+        }
+    }
+
+    /// Change the white extraction
+    pub fn set_extraction(&mut self, extraction: WhiteExtraction) {
+        self.extraction = extraction;
+    }
+
+    /// Change the channel order of the strip
+    pub fn set_order(&mut self, order: ColorOrder, white: WhitePosition) {
+        self.order = order;
+        self.white = white;
+    }
+
+    /// Get a reference to the driver
+    pub fn driver(&self) -> &D {
+        &self.driver
+    }
+}
+
+impl<D: RgbwOutputDriver, const N: usize> OutputDriver for RgbwOutput<D, N> {
+    fn write(&mut self, colors: &[Rgb]) {
+        let len = colors.len().min(N);
+        for (pixel, color) in self.frame.iter_mut().zip(colors) {
+            let mut pixel_rgbw = self.extraction.to_rgbw(*color);
+            if let Some(temperature) = self.temperature {
+                let whites = self.extraction.temperature_to_rgbw(*color);
+                pixel_rgbw = blend_rgbw(pixel_rgbw, whites, temperature.amount);
+            }
+            *pixel = self.order.apply_rgbw(pixel_rgbw, self.white);
+        }
+        self.driver.write_rgbw(&self.frame[..len]);
+    }
+
+    fn set_color_temperature(&mut self, temperature: Option<ColorTemperature>) {
+        self.temperature = temperature;
+    }
+}
+
+/// Output adapter feeding RGB frames to an RGB+CCT driver
+///
+/// Color temperatures are mixed from the warm and cool whites, colors drive
+/// the RGB channels, as set by the [`CctConfig`]. The channels are then put
+/// in the order of the strip. N is the maximum number of LEDs in the strip.
+pub struct RgbcctOutput<D: RgbcctOutputDriver, const N: usize> {
+    driver: D,
+    config: CctConfig,
+    order: ColorOrder,
+    whites: CctWhiteOrder,
+    temperature: Option<ColorTemperature>,
+    frame: [Rgbcct; N],
+}
+
+impl<D: RgbcctOutputDriver, const N: usize> RgbcctOutput<D, N> {
+    /// Create a new adapter
+    pub const fn new(driver: D, config: CctConfig) -> Self {
+        Self {
+            driver,
+            config,
+            order: ColorOrder::Rgb,
+            whites: CctWhiteOrder::ColdWarm,
+            temperature: None,
+            frame: [rgbcct(Rgb { r: 0, g: 0, b: 0 }, 0, 0); N],
+        }
+    }
+
+    /// Change the white channel configuration
+    pub fn set_config(&mut self, config: CctConfig) {
+        self.config = config;
+    }
+
+    /// Change the channel order of the strip
+    pub fn set_order(&mut self, order: ColorOrder, whites: CctWhiteOrder) {
+        self.order = order;
+        self.whites = whites;
+    }
+
+    /// Get a reference to the driver
+    pub fn driver(&self) -> &D {
+        &self.driver
+    }
+}
+
+impl<D: RgbcctOutputDriver, const N: usize> OutputDriver for RgbcctOutput<D, N> {
+    fn write(&mut self, colors: &[Rgb]) {
+        let len = colors.len().min(N);
+        for (pixel, color) in self.frame.iter_mut().zip(colors) {
+            let mut pixel_rgbcct = self.config.to_rgbcct(*color, None);
+            if let Some(temperature) = self.temperature {
+                let whites = self.config.to_rgbcct(*color, Some(temperature.kelvin));
+                pixel_rgbcct =
+                    blend_rgbcct(pixel_rgbcct, whites, temperature.amount);
+            }
+            *pixel = self.order.apply_rgbcct(pixel_rgbcct, self.whites);
+        }
+        self.driver.write_rgbcct(&self.frame[..len]);
+    }
+
+    fn set_color_temperature(&mut self, temperature: Option<ColorTemperature>) {
+        self.temperature = temperature;
+    }
+}
+
+/// Blend two RGBW pixels channel by channel
+fn blend_rgbw(a: Rgbw, b: Rgbw, amount_of_b: u8) -> Rgbw {
+    rgbw(
+        blend_colors(a.rgb(), b.rgb(), amount_of_b),
+        blend8(a.a.0, b.a.0, amount_of_b),
+    )
+}
+
+/// Blend two RGB+CCT pixels channel by channel
+fn blend_rgbcct(a: Rgbcct, b: Rgbcct, amount_of_b: u8) -> Rgbcct {
+    rgbcct(
+        blend_colors(a.rgb(), b.rgb(), amount_of_b),
+        blend8(a.a.warm, b.a.warm, amount_of_b),
+        blend8(a.a.cold, b.a.cold, amount_of_b),
+    )
+}
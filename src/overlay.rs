@@ -0,0 +1,152 @@
+//! Notification overlays
+//!
+//! An overlay temporarily replaces the rendered frame with an alert pattern,
+//! e.g. a doorbell blink. The light state underneath is left untouched, so
+//! the renderer resumes exactly where it was once the overlay ends.
+
+use embassy_time::Duration;
+
+use crate::{
+    color::Rgb,
+    math8::{scale8, sin8},
+};
+
+/// Period of one blink (on and off)
+const BLINK_PERIOD_MS: u64 = 500;
+/// Period of one pulse
+const PULSE_PERIOD_MS: u64 = 1_000;
+/// Length of one repeat of a solid overlay
+const SOLID_PERIOD_MS: u64 = 1_000;
+
+/// Alert pattern of an overlay
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlayPattern {
+    /// Hard on/off blinking
+    Blink,
+    /// Smooth fade in and out
+    Pulse,
+    /// Constant color
+    Solid,
+}
+
+impl OverlayPattern {
+    /// Length of one repeat of the pattern in milliseconds
+    const fn period_ms(self) -> u64 {
+        match self {
+            Self::Blink => BLINK_PERIOD_MS,
+            Self::Pulse => PULSE_PERIOD_MS,
+            Self::Solid => SOLID_PERIOD_MS,
+        }
+    }
+}
+
+/// How long an overlay is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlayLength {
+    /// Show for a fixed time
+    Duration(Duration),
+    /// Show the pattern a number of times
+    Repeat(u8),
+}
+
+/// Notification rendered on top of the current effect
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overlay {
+    pattern: OverlayPattern,
+    color: Rgb,
+    length: OverlayLength,
+    brightness: u8,
+}
+
+impl Overlay {
+    /// Create a new overlay at full brightness
+    pub const fn new(
+        pattern: OverlayPattern,
+        color: Rgb,
+        length: OverlayLength,
+    ) -> Self {
+        Self {
+            pattern,
+            color,
+            length,
+            brightness: 255,
+        }
+    }
+
+    /// Blink `color` a number of times
+    pub const fn blink(color: Rgb, times: u8) -> Self {
+        Self::new(OverlayPattern::Blink, color, OverlayLength::Repeat(times))
+    }
+
+    /// Pulse `color` for the given time
+    pub const fn pulse(color: Rgb, duration: Duration) -> Self {
+        Self::new(
+            OverlayPattern::Pulse,
+            color,
+            OverlayLength::Duration(duration),
+        )
+    }
+
+    /// Set the overlay brightness
+    ///
+    /// Overlays ignore the light brightness, so they are visible even when
+    /// the light is off.
+    #[must_use]
+    pub const fn with_brightness(mut self, brightness: u8) -> Self {
+        self.brightness = brightness;
+        self
+    }
+
+    /// Get the pattern
+    pub const fn pattern(&self) -> OverlayPattern {
+        self.pattern
+    }
+
+    /// Get the total time the overlay is shown
+    pub const fn duration(&self) -> Duration {
+        match self.length {
+            OverlayLength::Duration(duration) => duration,
+            OverlayLength::Repeat(times) => {
+                Duration::from_millis(times as u64 * self.pattern.period_ms())
+            }
+        }
+    }
+
+    /// Check if the overlay is over after `elapsed`
+    pub fn is_finished(&self, elapsed: Duration) -> bool {
+        elapsed >= self.duration()
+    }
+
+    /// Intensity of the pattern `elapsed` after the overlay started
+    #[allow(clippy::cast_possible_truncation)]
+    fn level(&self, elapsed: Duration) -> u8 {
+        let period = self.pattern.period_ms();
+        let phase = elapsed.as_millis() % period;
+        let level = match self.pattern {
+            OverlayPattern::Blink => {
+                if phase < period / 2 {
+                    255
+                } else {
+                    0
+                }
+            }
+            // Start dark, peak mid-period
+            OverlayPattern::Pulse => {
+                let angle = (phase * 256 / period) as u8;
+                255 - sin8(angle.wrapping_add(64))
+            }
+            OverlayPattern::Solid => 255,
+        };
+        scale8(level, self.brightness)
+    }
+
+    /// Render the overlay `elapsed` after it started
+    pub fn render(&self, elapsed: Duration, leds: &mut [Rgb]) {
+        let level = self.level(elapsed);
+        leds.fill(Rgb {
+            r: scale8(self.color.r, level),
+            g: scale8(self.color.g, level),
+            b: scale8(self.color.b, level),
+        });
+    }
+}
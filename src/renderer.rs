@@ -3,12 +3,40 @@ use embassy_time::{Duration, Instant};
 use esp_println::println;
 
 use crate::{
+    audio::{AudioFeed, AudioInput},
+    beat::{BeatSync, BeatTracker},
     bounds::{RenderingBounds, bounded},
-    color::Rgb,
-    effect::{EffectId, EffectSlot},
-    filter::{ColorCorrection, Filter, FilterProcessor, FilterProcessorConfig},
+    color::{COLOR_SLOTS, ColorSlot, Rgb, Rgb16, kelvin_to_rgb},
+    effect::{
+        CircadianCurve,
+        ClockStyle,
+        DEFAULT_BPM,
+        EffectId,
+        EffectSlot,
+        FlowPalette,
+        GarlandColors,
+        GarlandStyle,
+        GaugeStyle,
+        GradientConfig,
+        RainStyle,
+        StrobeStyle,
+    },
+    filter::{
+        ColorCorrection,
+        Filter,
+        FilterProcessor,
+        FilterProcessorConfig,
+        TemporalDither,
+        narrow_frame,
+        widen_frame,
+    },
     intent_processor::{IntentEffects, IntentProcessor, IntentReceiver},
+    math8::scale8,
     operation::{Operation, OperationStack},
+    output::ColorTemperature,
+    overlay::Overlay,
+    time_of_day::DayClock,
+    transition::{ColorBlending, ValueTransition},
 };
 
 /// Configuration for effect transitions
@@ -26,9 +54,27 @@ pub struct TransitionTimings {
 
 #[derive(Debug, Clone)]
 pub struct LightState {
-    color: Rgb,
+    /// User colors by slot, the primary slot is always set
+    colors: [Option<Rgb>; COLOR_SLOTS],
+    /// Color temperature of the primary color, if it was set in Kelvin
+    color_temperature: Option<u16>,
     current_effect: EffectSlot,
     brightness: u8,
+    clock: DayClock,
+    circadian_curve: CircadianCurve,
+    bpm: u16,
+    level: u16,
+    gauge_style: GaugeStyle,
+    clock_style: ClockStyle,
+    rain_style: RainStyle,
+    strobe_style: StrobeStyle,
+    gradient: Option<GradientConfig>,
+    flow_palette: Option<FlowPalette>,
+    /// Garland colors from the last intent, with later slot colors merged in
+    garland_colors: Option<GarlandColors>,
+    garland_style: GarlandStyle,
+    beat_sync: BeatSync,
+    color_blending: ColorBlending,
 }
 
 /// Configuration for the light engine
@@ -48,11 +94,22 @@ pub struct Renderer<'a, const MAX_LEDS: usize, const INTENT_CHANNEL_SIZE: usize>
     intent_processor: IntentProcessor<'a, INTENT_CHANNEL_SIZE>,
     timings: TransitionTimings,
     bounds: RenderingBounds,
+    audio: Option<AudioInput<'a>>,
 
     // Internal state
     state: LightState,
     stack: OperationStack<10>,
     frame_buffer: [Rgb; MAX_LEDS],
+    /// 16-bit copy of the frame the filters work on
+    wide_buffer: [Rgb16; MAX_LEDS],
+    overlay: Option<(Overlay, Instant)>,
+    beat: BeatTracker,
+    /// Dither state of the output, `None` if dithering is off
+    dither: Option<TemporalDither>,
+    /// Share of the light shown by white channels (0-255)
+    whites: ValueTransition<u8>,
+    /// Color temperature of the white channels in Kelvin
+    whites_kelvin: ValueTransition<u16>,
 
     // Internal dependencies
     filters: FilterProcessor,
@@ -71,49 +128,203 @@ impl<'a, const MAX_LEDS: usize, const INTENT_CHANNEL_SIZE: usize>
         Self {
             intent_processor: IntentProcessor::new(intents),
             frame_buffer: [Rgb::default(); MAX_LEDS],
+            wide_buffer: [Rgb16::default(); MAX_LEDS],
+            overlay: None,
+            beat: BeatTracker::new(),
+            dither: Some(TemporalDither::new()),
+            whites: ValueTransition::new_u8(0),
+            whites_kelvin: ValueTransition::new_u16(0),
             timings: config.timings,
             bounds: config.bounds,
+            audio: None,
             state: LightState {
-                color: config.color,
+                colors: [Some(config.color), None, None],
+                color_temperature: None,
                 current_effect: config.effect.to_slot(config.color),
                 brightness: config.brightness,
+                clock: DayClock::default(),
+                circadian_curve: CircadianCurve::default(),
+                bpm: DEFAULT_BPM,
+                level: 0,
+                gauge_style: GaugeStyle::default(),
+                clock_style: ClockStyle::default(),
+                rain_style: RainStyle::default(),
+                strobe_style: StrobeStyle::default(),
+                gradient: None,
+                flow_palette: None,
+                garland_colors: None,
+                garland_style: GarlandStyle::default(),
+                beat_sync: BeatSync::default(),
+                color_blending: ColorBlending::default(),
             },
             stack: OperationStack::new(),
             filters: FilterProcessor::new(&config.filters),
         }
     }
 
+    /// Read audio levels for audio-reactive effects from a feed
+    #[must_use]
+    pub const fn with_audio_feed(mut self, feed: &'a AudioFeed) -> Self {
+        self.audio = Some(AudioInput::new(feed));
+        self
+    }
+
+    /// Enable or disable temporal dithering of the output
+    ///
+    /// Dithering is on by default. It keeps slow fades at low brightness
+    /// smooth, at the cost of a slight flicker between neighboring levels
+    /// while fading. Steady brightness is shown without dithering.
+    #[must_use]
+    pub const fn with_dithering(mut self, enabled: bool) -> Self {
+        self.dither = if enabled {
+            Some(TemporalDither::new())
+        } else {
+            None
+        };
+        self
+    }
+
     /// Process one frame
     ///
     /// This is the main render loop step. Call this continuously.
     pub fn render(&mut self, now: Instant) -> &[Rgb] {
-        self.process_intents();
+        self.process_intents(now);
         self.process_operations(now);
 
         self.filters.tick(now);
+        self.whites.tick(now);
+        self.whites_kelvin.tick(now);
+
+        // Overlays bypass the effect and the brightness, the state underneath
+        // keeps running and is shown again once the overlay ends
+        if let Some((overlay, elapsed)) = self.current_overlay(now) {
+            let frame = bounded(&mut self.frame_buffer, self.bounds);
+            overlay.render(elapsed, frame);
+            let wide = bounded(&mut self.wide_buffer, self.bounds);
+            widen_frame(frame, wide);
+            self.filters.gamma.apply(wide);
+            self.filters.color_correction.apply(wide);
+            narrow_frame(wide, frame, None);
+            return &self.frame_buffer;
+        }
+
+        let mut energy = None;
+        if let Some(audio) = &mut self.audio {
+            let level = audio.level(now);
+            energy = Some(level.rms);
+            self.state.current_effect.set_audio_level(level, now);
+            if let Some(spectrum) = audio.spectrum(now) {
+                self.state.current_effect.set_spectrum(&spectrum);
+            }
+        }
+
+        let sync = self.state.beat_sync;
+        let beat = sync.is_enabled().then(|| self.beat.update(energy, now));
+        if sync.effects {
+            self.state.current_effect.set_beat(beat);
+        }
 
         let frame = bounded(&mut self.frame_buffer, self.bounds);
         self.state.current_effect.render(now, frame);
 
+        if let Some(beat) = beat.filter(|_| sync.brightness_kick > 0) {
+            let level = sync.brightness(&beat);
+            for led in frame.iter_mut() {
+                *led = Rgb {
+                    r: scale8(led.r, level),
+                    g: scale8(led.g, level),
+                    b: scale8(led.b, level),
+                };
+            }
+        }
+
+        // Filters work on 16 bits and the frame is rounded once at the end.
+        // Gamma goes before the linear stages, so brightness and dithering
+        // keep working on the corrected values
+        let wide = bounded(&mut self.wide_buffer, self.bounds);
+        widen_frame(frame, wide);
+        self.filters.gamma.apply(wide);
         if self.state.current_effect.requires_precise_colors() {
-            self.filters.color_correction.apply(frame);
+            self.filters.color_correction.apply(wide);
         }
-        self.filters.brightness.apply(frame);
+        self.filters.brightness.apply(wide);
+        let dither = self.dither.as_mut().map(|dither| {
+            dither.advance();
+            &*dither
+        });
+        let on_step = self.filters.brightness.is_on_step();
+        narrow_frame(wide, frame, dither.filter(|_| !on_step));
+
+        &self.frame_buffer
+    }
 
+    /// Get the last rendered frame
+    pub const fn frame(&self) -> &[Rgb] {
         &self.frame_buffer
     }
 
+    /// Get the color temperature shown by white channels
+    ///
+    /// The static effect shows a color set in Kelvin on the white channels,
+    /// crossfading from and to RGB over the color change duration. Returns
+    /// `None` while only RGB is used, e.g. for colors set by RGB, other
+    /// effects and overlays.
+    pub const fn color_temperature(&self) -> Option<ColorTemperature> {
+        let amount = self.whites.current();
+        if self.overlay.is_some() || amount == 0 {
+            return None;
+        }
+        Some(ColorTemperature {
+            kelvin: self.whites_kelvin.current(),
+            amount,
+        })
+    }
+
+    /// Check if a notification overlay is being shown
+    pub const fn is_overlay_active(&self) -> bool {
+        self.overlay.is_some()
+    }
+
+    /// Get the active overlay and the time since it started
+    ///
+    /// Clears the overlay once it is finished.
+    fn current_overlay(&mut self, now: Instant) -> Option<(Overlay, Duration)> {
+        let (overlay, started_at) = self.overlay?;
+        let elapsed = now.duration_since(started_at);
+        if overlay.is_finished(elapsed) {
+            self.overlay = None;
+            return None;
+        }
+        Some((overlay, elapsed))
+    }
+
     /// Process pending intents from the channel (non-blocking)
-    fn process_intents(&mut self) {
+    fn process_intents(&mut self, now: Instant) {
         let effects = self
             .intent_processor
             .process_pending(&mut self.stack, self.state.brightness);
 
-        self.apply_effects(&effects);
+        self.apply_effects(&effects, now);
     }
 
     /// Apply side effects from intent processing
-    fn apply_effects(&mut self, effects: &IntentEffects) {
+    fn apply_effects(&mut self, effects: &IntentEffects, now: Instant) {
+        if let Some(overlay) = effects.overlay {
+            self.overlay = overlay.map(|overlay| (overlay, now));
+        }
+
+        if let Some(blending) = effects.color_blending {
+            self.state.color_blending = blending;
+            self.state.current_effect.set_color_blending(blending);
+        }
+
+        if let Some(sync) = effects.beat_sync {
+            self.state.beat_sync = sync;
+            if !sync.effects {
+                self.state.current_effect.set_beat(None);
+            }
+        }
+
         if let Some(bounds) = effects.bounds {
             self.bounds = bounds;
         }
@@ -122,6 +333,10 @@ impl<'a, const MAX_LEDS: usize, const INTENT_CHANNEL_SIZE: usize>
             self.filters.color_correction = ColorCorrection::new(color_correction);
         }
 
+        if let Some(enabled) = effects.gamma {
+            self.filters.gamma.set_enabled(enabled);
+        }
+
         if let Some(brightness_range) = effects.brightness_range {
             self.filters
                 .brightness
@@ -132,11 +347,80 @@ impl<'a, const MAX_LEDS: usize, const INTENT_CHANNEL_SIZE: usize>
         if let Some(adjuster) = effects.adjuster {
             self.filters.brightness.set_adjuster(adjuster);
         }
+
+        if let Some(time_of_day) = effects.time_of_day {
+            self.state.clock = DayClock::new(time_of_day, now);
+            self.state.current_effect.set_clock(self.state.clock);
+        }
+
+        if let Some(curve) = &effects.circadian_curve {
+            self.state.circadian_curve = curve.clone();
+            self.state.current_effect.set_circadian_curve(curve);
+        }
+
+        if let Some(bpm) = effects.bpm {
+            self.state.bpm = bpm;
+            self.state.current_effect.set_bpm(bpm);
+            self.beat.set_fallback_bpm(bpm);
+        }
+
+        if let Some(level) = effects.level {
+            self.state.level = level;
+            self.state.current_effect.set_level(level, now);
+        }
+
+        if let Some(style) = effects.gauge_style {
+            self.state.gauge_style = style;
+            self.state.current_effect.set_gauge_style(style);
+        }
+
+        if let Some(style) = effects.clock_style {
+            self.state.clock_style = style;
+            self.state.current_effect.set_clock_style(style);
+        }
+
+        if let Some(style) = effects.rain_style {
+            self.state.rain_style = style;
+            self.state.current_effect.set_rain_style(style);
+        }
+
+        if let Some(style) = effects.strobe_style {
+            self.state.strobe_style = style;
+            self.state.current_effect.set_strobe_style(style);
+        }
+
+        if let Some(config) = &effects.gradient {
+            self.state.gradient = Some(config.clone());
+            self.state.current_effect.set_gradient(config);
+        }
+
+        if let Some(palette) = &effects.flow_palette {
+            self.state.flow_palette.clone_from(palette);
+            self.state.current_effect.set_flow_palette(
+                palette.as_ref(),
+                self.timings.color_change,
+                now,
+            );
+        }
+
+        if let Some(colors) = &effects.garland_colors {
+            self.state.garland_colors = Some(colors.clone());
+            self.state.current_effect.set_garland_colors(
+                colors,
+                self.timings.color_change,
+                now,
+            );
+        }
+
+        if let Some(style) = effects.garland_style {
+            self.state.garland_style = style;
+            self.state.current_effect.set_garland_style(style);
+        }
     }
 
     /// Process the next operation from the stack
     fn process_operations(&mut self, now: Instant) {
-        let Some(next) = self.process_current_operation() else {
+        let Some(next) = self.process_current_operation(now) else {
             return;
         };
         // Start the transition for the current operation
@@ -148,12 +432,18 @@ impl<'a, const MAX_LEDS: usize, const INTENT_CHANNEL_SIZE: usize>
                     now,
                 );
             }
-            Operation::SetColor(color) => {
-                self.state.current_effect.set_color(
-                    color,
-                    self.timings.color_change,
-                    now,
-                );
+            Operation::SetColor(slot, color, blending) => {
+                if slot == ColorSlot::Primary {
+                    self.state.color_temperature = None;
+                    self.update_whites(now);
+                }
+                self.start_color_change(slot, color, blending, now);
+            }
+            Operation::SetColorTemperature(kelvin, blending) => {
+                self.state.color_temperature = Some(kelvin);
+                self.update_whites(now);
+                let color = kelvin_to_rgb(kelvin);
+                self.start_color_change(ColorSlot::Primary, color, blending, now);
             }
             Operation::PowerOff => {
                 self.filters.brightness.set_uncorrected(
@@ -175,16 +465,83 @@ impl<'a, const MAX_LEDS: usize, const INTENT_CHANNEL_SIZE: usize>
         }
     }
 
+    /// Start a color transition in the current effect
+    fn start_color_change(
+        &mut self,
+        slot: ColorSlot,
+        color: Rgb,
+        blending: Option<ColorBlending>,
+        now: Instant,
+    ) {
+        self.state
+            .current_effect
+            .set_color_blending(blending.unwrap_or(self.state.color_blending));
+        self.state.current_effect.set_slot_color(
+            slot,
+            color,
+            self.timings.color_change,
+            now,
+        );
+    }
+
+    /// Remember a slot color for the effects switched to later
+    ///
+    /// The color is merged into the stored styles that hold slot colors, so
+    /// the latest of a style and a slot color wins.
+    fn store_slot_color(&mut self, slot: ColorSlot, color: Rgb) {
+        self.state.colors[slot.index()] = Some(color);
+        self.state.gauge_style.set_color_at(slot.index(), color);
+        self.state.clock_style.set_color_at(slot.index(), color);
+        if let Some(colors) = &mut self.state.garland_colors {
+            colors.set_color_at(slot.index(), color);
+        }
+    }
+
+    /// Fade the white channels in or out for the current state
+    ///
+    /// Only the static effect shows the color temperature on the whites.
+    fn update_whites(&mut self, now: Instant) {
+        let kelvin = match (&self.state.current_effect, self.state.color_temperature)
+        {
+            (EffectSlot::Static(_), Some(kelvin)) => Some(kelvin),
+            _ => None,
+        };
+        if let Some(kelvin) = kelvin {
+            // Fading in from RGB starts at the new temperature right away
+            let duration = if self.whites.current() == 0 {
+                Duration::from_millis(0)
+            } else {
+                self.timings.color_change
+            };
+            self.whites_kelvin.set(kelvin, duration, now);
+        }
+        let target = if kelvin.is_some() { 255 } else { 0 };
+        self.whites.set(target, self.timings.color_change, now);
+    }
+
+    /// Finish a color transition started by [`Self::start_color_change`]
+    ///
+    /// A blending override only applies to its own transition.
+    fn end_color_change(&mut self, blending: Option<ColorBlending>) {
+        if blending.is_some() {
+            self.state
+                .current_effect
+                .set_color_blending(self.state.color_blending);
+        }
+    }
+
     /// Process the current operation from the stack
     ///
     /// Returns the next operation to process
-    fn process_current_operation(&mut self) -> Option<Operation> {
+    fn process_current_operation(&mut self, now: Instant) -> Option<Operation> {
         let current = self.stack.current()?;
         let is_complete = match current {
             Operation::SetBrightness(_)
             | Operation::PowerOff
             | Operation::PowerOn => !self.filters.brightness.is_transitioning(),
-            Operation::SetColor(_) => !self.state.current_effect.is_transitioning(),
+            Operation::SetColor(..) | Operation::SetColorTemperature(..) => {
+                !self.state.current_effect.is_transitioning()
+            }
             Operation::SwitchEffect(_) => true,
         };
         if !is_complete {
@@ -195,11 +552,16 @@ impl<'a, const MAX_LEDS: usize, const INTENT_CHANNEL_SIZE: usize>
             Operation::SetBrightness(brightness) => {
                 self.state.brightness = brightness;
             }
-            Operation::SetColor(color) => {
-                self.state.color = color;
+            Operation::SetColor(slot, color, blending) => {
+                self.store_slot_color(slot, color);
+                self.end_color_change(blending);
+            }
+            Operation::SetColorTemperature(kelvin, blending) => {
+                self.store_slot_color(ColorSlot::Primary, kelvin_to_rgb(kelvin));
+                self.end_color_change(blending);
             }
             Operation::SwitchEffect(effect) => {
-                self.set_effect(effect);
+                self.set_effect(effect, now);
             }
             Operation::PowerOff | Operation::PowerOn => {
                 // This commands does not change the state
@@ -210,8 +572,68 @@ impl<'a, const MAX_LEDS: usize, const INTENT_CHANNEL_SIZE: usize>
     }
 
     /// Set new effect by id
-    fn set_effect(&mut self, effect: EffectId) {
-        self.state.current_effect = effect.to_slot(self.state.color);
+    ///
+    /// Effect parameters received earlier are re-applied to the new effect.
+    fn set_effect(&mut self, effect: EffectId, now: Instant) {
+        let primary = self.state.colors[ColorSlot::Primary.index()];
+        self.state.current_effect = effect.to_slot(primary.unwrap_or_default());
         self.state.current_effect.reset();
+        self.apply_slot_colors(now);
+        self.state.current_effect.set_clock(self.state.clock);
+        self.state
+            .current_effect
+            .set_circadian_curve(&self.state.circadian_curve);
+        self.state.current_effect.set_bpm(self.state.bpm);
+        self.state
+            .current_effect
+            .set_gauge_style(self.state.gauge_style);
+        self.state
+            .current_effect
+            .set_clock_style(self.state.clock_style);
+        self.state
+            .current_effect
+            .set_rain_style(self.state.rain_style);
+        self.state
+            .current_effect
+            .set_strobe_style(self.state.strobe_style);
+        if let Some(config) = &self.state.gradient {
+            self.state.current_effect.set_gradient(config);
+        }
+        self.state.current_effect.set_flow_palette(
+            self.state.flow_palette.as_ref(),
+            Duration::from_millis(0),
+            now,
+        );
+        if let Some(colors) = &self.state.garland_colors {
+            self.state.current_effect.set_garland_colors(
+                colors,
+                Duration::from_millis(0),
+                now,
+            );
+        }
+        self.state
+            .current_effect
+            .set_garland_style(self.state.garland_style);
+        self.state.current_effect.set_level(self.state.level, now);
+        self.state
+            .current_effect
+            .set_color_blending(self.state.color_blending);
+        self.update_whites(now);
+    }
+
+    /// Apply the stored slot colors to the current effect instantly
+    ///
+    /// Styles applied afterwards override them with their own colors.
+    fn apply_slot_colors(&mut self, now: Instant) {
+        for slot in ColorSlot::ALL {
+            if let Some(color) = self.state.colors[slot.index()] {
+                self.state.current_effect.set_slot_color(
+                    slot,
+                    color,
+                    Duration::from_millis(0),
+                    now,
+                );
+            }
+        }
     }
 }
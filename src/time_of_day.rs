@@ -0,0 +1,113 @@
+//! Wall-clock time of day
+//!
+//! The library never reads clocks, so time of day is supplied by the caller
+//! and anchored to the monotonic `Instant` used for rendering. Between syncs
+//! the time of day advances together with `now`.
+
+use embassy_time::{Duration, Instant};
+
+/// Milliseconds in one day
+pub const MS_PER_DAY: u32 = 24 * 60 * 60 * 1000;
+
+/// Time of day with millisecond resolution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct TimeOfDay(u32);
+
+impl TimeOfDay {
+    /// Midnight (00:00:00.000)
+    pub const MIDNIGHT: Self = Self(0);
+
+    /// Noon (12:00:00.000)
+    pub const NOON: Self = Self(MS_PER_DAY / 2);
+
+    /// Create a time of day from milliseconds since midnight
+    ///
+    /// Values of a day or more wrap around.
+    pub const fn from_millis(ms: u32) -> Self {
+        Self(ms % MS_PER_DAY)
+    }
+
+    /// Create a time of day from hours, minutes and seconds
+    ///
+    /// Out-of-range values wrap around.
+    pub const fn from_hms(hours: u8, minutes: u8, seconds: u8) -> Self {
+        let ms = (hours as u32 * 3600 + minutes as u32 * 60 + seconds as u32) * 1000;
+        Self::from_millis(ms)
+    }
+
+    /// Create a time of day from minutes since midnight
+    pub const fn from_minutes(minutes: u16) -> Self {
+        Self::from_millis(minutes as u32 * 60_000)
+    }
+
+    /// Get milliseconds since midnight
+    pub const fn as_millis(self) -> u32 {
+        self.0
+    }
+
+    /// Get whole minutes since midnight (0-1439)
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn as_minutes(self) -> u16 {
+        (self.0 / 60_000) as u16
+    }
+
+    /// Get the hour (0-23)
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn hours(self) -> u8 {
+        (self.0 / 3_600_000) as u8
+    }
+
+    /// Get the minute within the hour (0-59)
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn minutes(self) -> u8 {
+        ((self.0 / 60_000) % 60) as u8
+    }
+
+    /// Get the second within the minute (0-59)
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn seconds(self) -> u8 {
+        ((self.0 / 1000) % 60) as u8
+    }
+
+    /// Advance the time of day by a duration, wrapping at midnight
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn add(self, duration: Duration) -> Self {
+        let delta = (duration.as_millis() % MS_PER_DAY as u64) as u32;
+        Self::from_millis(self.0 + delta)
+    }
+}
+
+/// Time of day anchored to a render instant
+///
+/// Stores the time of day reported by the caller together with the instant it
+/// was reported at, so the current time of day can be derived from `now`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayClock {
+    time_of_day: TimeOfDay,
+    synced_at: Instant,
+}
+
+impl Default for DayClock {
+    /// Clock that assumes `Instant` zero was midnight
+    fn default() -> Self {
+        Self::new(TimeOfDay::MIDNIGHT, Instant::from_millis(0))
+    }
+}
+
+impl DayClock {
+    /// Create a clock that reads `time_of_day` at `now`
+    pub const fn new(time_of_day: TimeOfDay, now: Instant) -> Self {
+        Self {
+            time_of_day,
+            synced_at: now,
+        }
+    }
+
+    /// Get the time of day at `now`
+    pub fn at(&self, now: Instant) -> TimeOfDay {
+        let elapsed = now.checked_duration_since(self.synced_at);
+        self.time_of_day
+            .add(elapsed.unwrap_or(Duration::from_millis(0)))
+    }
+}
@@ -1,8 +1,16 @@
 use embassy_time::{Duration, Instant};
 
 use crate::{
-    color::{Rgb, blend_colors},
-    math8::{blend8, progress8},
+    color::{
+        GradientDirection,
+        Rgb,
+        blend_colors,
+        blend_hsv,
+        blend_oklab,
+        hsv2rgb,
+        rgb2hsv,
+    },
+    math8::{blend8, blend16, progress8},
 };
 
 /// Blends two values of type `T` using a progress value (0-255)
@@ -65,6 +73,13 @@ impl<T: Copy> ValueTransition<T> {
         }
     }
 
+    /// Replace the blender function
+    ///
+    /// A transition in progress continues with the new blender.
+    pub fn set_blender(&mut self, blend: ValueBlender<T>) {
+        self.blend = blend;
+    }
+
     /// Update transition state
     ///
     /// Call this once per frame with the frame delta time.
@@ -93,9 +108,63 @@ impl ValueTransition<u8> {
     }
 }
 
+impl ValueTransition<u16> {
+    /// Create a new u16 transition
+    pub const fn new_u16(initial: u16) -> Self {
+        Self::new(initial, blend16)
+    }
+}
+
 impl ValueTransition<Rgb> {
     /// Create a new rgb transition
     pub const fn new_rgb(initial: Rgb) -> Self {
         Self::new(initial, blend_colors)
     }
 }
+
+/// Blend two RGB colors through HSV
+///
+/// The ends are returned as is, since the HSV round trip is lossy.
+fn blend_rgb_hsv(
+    a: Rgb,
+    b: Rgb,
+    amount_of_b: u8,
+    direction: GradientDirection,
+) -> Rgb {
+    match amount_of_b {
+        0 => a,
+        255 => b,
+        _ => hsv2rgb(blend_hsv(rgb2hsv(a), rgb2hsv(b), amount_of_b, direction)),
+    }
+}
+
+/// Color space used to blend color transitions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorBlending {
+    /// Blend gamma-encoded sRGB values, cheapest
+    #[default]
+    Srgb,
+    /// Blend in the Oklab color space, perceptually even
+    Oklab,
+    /// Blend hue, saturation and value, going around the hue wheel
+    Hsv(GradientDirection),
+}
+
+impl ColorBlending {
+    /// Get the blender function of this color space
+    pub const fn blender(self) -> ValueBlender<Rgb> {
+        match self {
+            Self::Srgb => blend_colors,
+            Self::Oklab => blend_oklab,
+            Self::Hsv(GradientDirection::Forward) => {
+                |a, b, t| blend_rgb_hsv(a, b, t, GradientDirection::Forward)
+            }
+            Self::Hsv(GradientDirection::Backward) => {
+                |a, b, t| blend_rgb_hsv(a, b, t, GradientDirection::Backward)
+            }
+            Self::Hsv(GradientDirection::Shortest) => {
+                |a, b, t| blend_rgb_hsv(a, b, t, GradientDirection::Shortest)
+            }
+        }
+    }
+}
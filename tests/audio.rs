@@ -0,0 +1,99 @@
+mod common;
+
+mod tests {
+    use myrtio_light_composer::{
+        AudioFeed,
+        AudioInput,
+        AudioLevel,
+        EffectId,
+        Instant,
+        IntentChannel,
+        LightChangeIntent,
+        LightStateIntent,
+        Renderer,
+        color::Rgb,
+        effect::{Effect, VuMeterEffect},
+    };
+
+    use crate::common;
+
+    const BLACK: Rgb = Rgb { r: 0, g: 0, b: 0 };
+
+    #[test]
+    fn test_feed_keeps_latest_level() {
+        let feed = AudioFeed::new();
+        let mut input = AudioInput::new(&feed);
+        assert_eq!(input.level(Instant::from_millis(0)), AudioLevel::SILENCE);
+
+        feed.update(AudioLevel::new(100, 50));
+        feed.update(AudioLevel::new(200, 120));
+        assert_eq!(
+            input.level(Instant::from_millis(10)),
+            AudioLevel::new(200, 120)
+        );
+    }
+
+    #[test]
+    fn test_stale_input_decays_to_silence() {
+        let feed = AudioFeed::new();
+        let mut input = AudioInput::new(&feed);
+        feed.update(AudioLevel::new(200, 200));
+        assert_eq!(input.level(Instant::from_millis(0)).rms, 200);
+        assert_eq!(input.level(Instant::from_millis(200)).rms, 200);
+
+        let fading = input.level(Instant::from_millis(450)).rms;
+        assert!(fading > 0 && fading < 200);
+        assert_eq!(
+            input.level(Instant::from_millis(1_000)),
+            AudioLevel::SILENCE
+        );
+
+        // Repeating the same level counts as fresh input
+        feed.update(AudioLevel::new(200, 200));
+        assert_eq!(input.level(Instant::from_millis(1_010)).rms, 200);
+    }
+
+    #[test]
+    fn test_vu_meter_bar_and_peak_hold() {
+        let mut effect = VuMeterEffect::new();
+        let mut leds = [BLACK; 10];
+        effect.set_level(AudioLevel::new(255, 128), Instant::from_millis(0));
+        effect.render(Instant::from_millis(0), &mut leds);
+
+        // Green bottom, red top, half the strip lit plus the peak marker
+        assert_eq!(leds[0], Rgb { r: 0, g: 255, b: 0 });
+        assert!(leds[4].g > 0);
+        assert_eq!(leds[6], BLACK);
+        assert_eq!(leds[9], Rgb { r: 255, g: 0, b: 0 });
+
+        // Bar falls, peak is held
+        effect.render(Instant::from_millis(300), &mut leds);
+        assert!(effect.bar_level() < 20);
+        assert_eq!(effect.peak_level(), 255);
+
+        // Peak falls after the hold
+        effect.render(Instant::from_millis(1_300), &mut leds);
+        assert!(effect.peak_level() < 255);
+        effect.render(Instant::from_millis(3_000), &mut leds);
+        assert_eq!(leds, [BLACK; 10]);
+    }
+
+    #[test]
+    fn test_renderer_reads_audio_feed() {
+        let feed = AudioFeed::new();
+        let intents = IntentChannel::<8>::new();
+        let config = common::config(EffectId::VuMeter, 4, BLACK);
+        let mut renderer = Renderer::<4, 8>::new(intents.receiver(), &config)
+            .with_audio_feed(&feed);
+        let sender = intents.sender();
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            power: Some(true),
+            ..Default::default()
+        }));
+        renderer.render(Instant::from_millis(0));
+
+        feed.update(AudioLevel::new(255, 255));
+        let frame = renderer.render(Instant::from_millis(10));
+        assert!(frame.iter().all(|led| *led != BLACK));
+    }
+}
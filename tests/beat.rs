@@ -0,0 +1,113 @@
+mod common;
+
+mod tests {
+    use myrtio_light_composer::{
+        BeatDetector,
+        BeatSync,
+        BeatTracker,
+        EffectId,
+        Instant,
+        IntentChannel,
+        LightChangeIntent,
+        LightStateIntent,
+        Renderer,
+        color::Rgb,
+    };
+
+    use crate::common;
+
+    const WHITE: Rgb = Rgb {
+        r: 255,
+        g: 255,
+        b: 255,
+    };
+
+    /// Energy with a spike every `period_ms`, sampled every 10ms
+    fn energy_at(ms: u64, period_ms: u64) -> u8 {
+        if ms % period_ms < 50 { 200 } else { 20 }
+    }
+
+    #[test]
+    fn test_detector_estimates_tempo() {
+        let mut detector = BeatDetector::new();
+        let mut beats = 0;
+        for ms in (0..5_000).step_by(10) {
+            if detector.update(energy_at(ms, 500), Instant::from_millis(ms)) {
+                beats += 1;
+            }
+        }
+        assert_eq!(beats, 10);
+        assert_eq!(detector.bpm(), Some(120));
+    }
+
+    #[test]
+    fn test_detector_ignores_steady_energy() {
+        let mut detector = BeatDetector::new();
+        for ms in (0..2_000).step_by(10) {
+            detector.update(150, Instant::from_millis(ms));
+        }
+        let beats = (2_000..4_000)
+            .step_by(10)
+            .filter(|ms| detector.update(150, Instant::from_millis(*ms)))
+            .count();
+        assert_eq!(beats, 0);
+        assert_eq!(detector.bpm(), None);
+    }
+
+    #[test]
+    fn test_tracker_falls_back_to_bpm_grid() {
+        let mut tracker = BeatTracker::new();
+        tracker.set_fallback_bpm(120);
+
+        let beat = tracker.update(None, Instant::from_millis(0));
+        assert_eq!(beat.pulse, 255);
+        assert_eq!(beat.count, 1);
+        assert_eq!(beat.bpm, 120);
+
+        let decaying = tracker.update(None, Instant::from_millis(90)).pulse;
+        assert!(decaying > 0 && decaying < 255);
+        assert_eq!(tracker.update(None, Instant::from_millis(200)).pulse, 0);
+
+        let beat = tracker.update(None, Instant::from_millis(500));
+        assert_eq!(beat.pulse, 255);
+        assert_eq!(beat.count, 2);
+    }
+
+    #[test]
+    fn test_tracker_follows_audio() {
+        let mut tracker = BeatTracker::new();
+        tracker.set_fallback_bpm(60);
+        let mut beat = tracker.update(Some(0), Instant::from_millis(0));
+        for ms in (10..4_000).step_by(10) {
+            beat =
+                tracker.update(Some(energy_at(ms, 400)), Instant::from_millis(ms));
+        }
+        assert_eq!(beat.bpm, 150);
+        assert!(beat.count >= 9);
+    }
+
+    #[test]
+    fn test_renderer_brightness_kick() {
+        let intents = IntentChannel::<8>::new();
+        let config = common::config(EffectId::Static, 4, WHITE);
+        let mut renderer = Renderer::<4, 8>::new(intents.receiver(), &config);
+        let sender = intents.sender();
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            power: Some(true),
+            ..Default::default()
+        }));
+        let _ = sender.try_send(LightChangeIntent::Bpm(60));
+        let _ = sender.try_send(LightChangeIntent::BeatSync(BeatSync {
+            effects: false,
+            brightness_kick: 255,
+        }));
+
+        // Full brightness on the beat, dark in between
+        assert_eq!(renderer.render(Instant::from_millis(0))[0], WHITE);
+        assert_eq!(renderer.render(Instant::from_millis(500))[0].r, 0);
+        assert_eq!(renderer.render(Instant::from_millis(1_000))[0], WHITE);
+
+        let _ = sender.try_send(LightChangeIntent::BeatSync(BeatSync::default()));
+        assert_eq!(renderer.render(Instant::from_millis(1_500))[0], WHITE);
+    }
+}
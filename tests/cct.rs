@@ -0,0 +1,213 @@
+mod common;
+
+mod tests {
+    use myrtio_light_composer::{
+        ColorTemperature,
+        Duration,
+        EffectId,
+        FrameScheduler,
+        Instant,
+        IntentChannel,
+        LightChangeIntent,
+        LightEngineConfig,
+        LightStateIntent,
+        Renderer,
+        RgbcctOutput,
+        RgbcctOutputDriver,
+        color::{
+            CctBlend,
+            CctConfig,
+            ColorMix,
+            Rgb,
+            Rgbcct,
+            TemperatureMix,
+            rgbcct,
+        },
+    };
+
+    use crate::common;
+
+    const BLACK: Rgb = Rgb { r: 0, g: 0, b: 0 };
+    const WHITE: Rgb = Rgb {
+        r: 255,
+        g: 255,
+        b: 255,
+    };
+
+    #[derive(Default)]
+    struct Recorder {
+        frame: Vec<Rgbcct>,
+    }
+
+    impl RgbcctOutputDriver for Recorder {
+        fn write_rgbcct(&mut self, colors: &[Rgbcct]) {
+            self.frame = colors.to_vec();
+        }
+    }
+
+    fn config() -> LightEngineConfig {
+        common::config(EffectId::Static, 2, WHITE)
+    }
+
+    #[test]
+    fn test_mix_whites() {
+        let cct = CctConfig::default();
+        assert_eq!(cct.mix_whites(2_700, 200), (200, 0));
+        assert_eq!(cct.mix_whites(6_500, 200), (0, 200));
+        assert_eq!(cct.mix_whites(1_800, 200), (200, 0));
+
+        let (warm, cool) = cct.mix_whites(4_600, 255);
+        assert_eq!(u16::from(warm) + u16::from(cool), 255);
+        assert!(warm.abs_diff(cool) < 4);
+    }
+
+    #[test]
+    fn test_blend_policies() {
+        let orange = Rgb::new(255, 150, 100);
+        let whites_only = CctConfig::default();
+        assert_eq!(
+            whites_only.to_rgbcct(orange, Some(2_700)),
+            rgbcct(BLACK, 255, 0)
+        );
+        assert_eq!(whites_only.to_rgbcct(orange, None), rgbcct(orange, 0, 0));
+
+        let mixed = CctConfig {
+            blend: CctBlend {
+                temperature: TemperatureMix::WhitesAndRgb,
+                color: ColorMix::ExtractWhite,
+            },
+            ..CctConfig::default()
+        };
+        assert_eq!(mixed.to_rgbcct(orange, Some(6_500)), rgbcct(orange, 0, 255));
+
+        let pixel = mixed.to_rgbcct(orange, None);
+        assert_eq!((pixel.r, pixel.g, pixel.b), (155, 50, 0));
+        assert_eq!(u16::from(pixel.a.warm) + u16::from(pixel.a.cold), 100);
+    }
+
+    #[test]
+    fn test_renderer_tracks_temperature() {
+        let intents = IntentChannel::<8>::new();
+        let mut renderer = Renderer::<2, 8>::new(intents.receiver(), &config());
+        let sender = intents.sender();
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            power: Some(true),
+            color_temperature: Some(3_000),
+            ..Default::default()
+        }));
+        renderer.render(Instant::from_millis(0));
+        assert_eq!(
+            renderer.color_temperature(),
+            Some(ColorTemperature {
+                kelvin: 3_000,
+                amount: 255,
+            })
+        );
+
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            color: Some(Rgb::new(255, 0, 0)),
+            ..Default::default()
+        }));
+        for ms in [10, 20, 30] {
+            renderer.render(Instant::from_millis(ms));
+        }
+        assert_eq!(renderer.color_temperature(), None);
+    }
+
+    #[test]
+    fn test_scheduler_mixes_whites() {
+        let intents = IntentChannel::<8>::new();
+        let renderer = Renderer::<2, 8>::new(intents.receiver(), &config());
+        let output = RgbcctOutput::<Recorder, 2>::new(
+            Recorder::default(),
+            CctConfig::default(),
+        );
+        let mut scheduler = FrameScheduler::new(renderer, output);
+        let sender = intents.sender();
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            power: Some(true),
+            color_temperature: Some(6_500),
+            ..Default::default()
+        }));
+        scheduler.tick(Instant::from_millis(0));
+        scheduler.tick(Instant::from_millis(20));
+
+        let frame = &scheduler.output().driver().frame;
+        assert_eq!(frame.len(), 2);
+        assert_eq!(frame[0], rgbcct(BLACK, 0, 255));
+    }
+
+    #[test]
+    fn test_effect_switch_hands_temperature_to_rgb() {
+        let intents = IntentChannel::<8>::new();
+        let mut renderer = Renderer::<2, 8>::new(intents.receiver(), &config());
+        let sender = intents.sender();
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            power: Some(true),
+            color_temperature: Some(3_000),
+            ..Default::default()
+        }));
+        renderer.render(Instant::from_millis(0));
+        assert!(renderer.color_temperature().is_some());
+
+        // Other effects render on RGB only
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            effect_id: Some(EffectId::Garland),
+            ..Default::default()
+        }));
+        for ms in 10..20 {
+            renderer.render(Instant::from_millis(ms));
+        }
+        assert_eq!(renderer.color_temperature(), None);
+
+        // Back on the static effect the whites show the temperature again
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            effect_id: Some(EffectId::Static),
+            ..Default::default()
+        }));
+        for ms in 20..30 {
+            renderer.render(Instant::from_millis(ms));
+        }
+        assert_eq!(
+            renderer
+                .color_temperature()
+                .map(|temperature| temperature.kelvin),
+            Some(3_000)
+        );
+    }
+
+    #[test]
+    fn test_scheduler_crossfades_into_whites() {
+        let intents = IntentChannel::<8>::new();
+        let mut config = config();
+        config.timings.color_change = Duration::from_millis(1_000);
+        let renderer = Renderer::<2, 8>::new(intents.receiver(), &config);
+        let output = RgbcctOutput::<Recorder, 2>::new(
+            Recorder::default(),
+            CctConfig::default(),
+        );
+        let mut scheduler = FrameScheduler::new(renderer, output);
+        let sender = intents.sender();
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            power: Some(true),
+            ..Default::default()
+        }));
+        for ms in 0..5 {
+            scheduler.tick(Instant::from_millis(ms));
+        }
+        assert_eq!(scheduler.output().driver().frame[0], rgbcct(WHITE, 0, 0));
+
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            color_temperature: Some(6_500),
+            ..Default::default()
+        }));
+        scheduler.tick(Instant::from_millis(5));
+        scheduler.tick(Instant::from_millis(505));
+        let halfway = scheduler.output().driver().frame[0];
+        assert!(halfway.r > 64 && halfway.r < 192);
+        assert!(halfway.a.cold > 64 && halfway.a.cold < 192);
+
+        scheduler.tick(Instant::from_millis(1_100));
+        assert_eq!(scheduler.output().driver().frame[0], rgbcct(BLACK, 0, 255));
+    }
+}
@@ -0,0 +1,100 @@
+mod tests {
+    use myrtio_light_composer::{
+        Instant,
+        color::{Rgb, kelvin_to_rgb},
+        effect::{CircadianCurve, CircadianEffect, CircadianPoint, Effect},
+        time_of_day::{DayClock, TimeOfDay},
+    };
+
+    fn curve() -> CircadianCurve {
+        CircadianCurve::from_points(&[
+            CircadianPoint::new(18, 0, 3000, 100),
+            CircadianPoint::new(6, 0, 2000, 0),
+            CircadianPoint::new(12, 0, 6000, 255),
+        ])
+    }
+
+    #[test]
+    fn test_time_of_day() {
+        let time = TimeOfDay::from_hms(13, 45, 30);
+        assert_eq!(time.hours(), 13);
+        assert_eq!(time.minutes(), 45);
+        assert_eq!(time.seconds(), 30);
+        assert_eq!(time.as_minutes(), 13 * 60 + 45);
+        assert_eq!(TimeOfDay::from_hms(24, 0, 0), TimeOfDay::MIDNIGHT);
+    }
+
+    #[test]
+    fn test_day_clock_wraps_at_midnight() {
+        let clock = DayClock::new(
+            TimeOfDay::from_hms(23, 59, 0),
+            Instant::from_millis(1000),
+        );
+        assert_eq!(
+            clock.at(Instant::from_millis(1000)),
+            TimeOfDay::from_hms(23, 59, 0)
+        );
+        assert_eq!(
+            clock.at(Instant::from_millis(121_000)),
+            TimeOfDay::from_hms(0, 1, 0)
+        );
+        // Instants before the sync point read the synced time
+        assert_eq!(
+            clock.at(Instant::from_millis(0)),
+            TimeOfDay::from_hms(23, 59, 0)
+        );
+    }
+
+    #[test]
+    fn test_curve_sorted() {
+        let minutes: Vec<u16> = curve().points().iter().map(|p| p.minute).collect();
+        assert_eq!(minutes, [6 * 60, 12 * 60, 18 * 60]);
+    }
+
+    #[test]
+    fn test_curve_sample_points() {
+        let curve = curve();
+        assert_eq!(curve.sample(TimeOfDay::from_hms(6, 0, 0)), (2000, 0));
+        assert_eq!(curve.sample(TimeOfDay::NOON), (6000, 255));
+        assert_eq!(curve.sample(TimeOfDay::from_hms(18, 0, 0)), (3000, 100));
+    }
+
+    #[test]
+    fn test_curve_sample_interpolates() {
+        let (kelvin, brightness) = curve().sample(TimeOfDay::from_hms(9, 0, 0));
+        assert!((3980..=4020).contains(&kelvin), "kelvin = {kelvin}");
+        assert!(
+            (126..=129).contains(&brightness),
+            "brightness = {brightness}"
+        );
+    }
+
+    #[test]
+    fn test_curve_sample_wraps_midnight() {
+        // 18:00 -> 06:00 spans midnight, which is the halfway point
+        let (kelvin, brightness) = curve().sample(TimeOfDay::MIDNIGHT);
+        assert!((2480..=2520).contains(&kelvin), "kelvin = {kelvin}");
+        assert!((48..=52).contains(&brightness), "brightness = {brightness}");
+    }
+
+    #[test]
+    fn test_empty_curve() {
+        let curve = CircadianCurve::from_points(&[]);
+        assert_eq!(curve.sample(TimeOfDay::NOON), (6500, 255));
+    }
+
+    #[test]
+    fn test_circadian_effect_render() {
+        let mut effect = CircadianEffect::new().with_curve(curve());
+        effect.set_clock(DayClock::new(TimeOfDay::NOON, Instant::from_millis(0)));
+
+        let mut leds = [Rgb::default(); 4];
+        effect.render(Instant::from_millis(0), &mut leds);
+        assert_eq!(leds, [kelvin_to_rgb(6000); 4]);
+
+        // Six hours later the light is at its evening point
+        effect.render(Instant::from_millis(6 * 60 * 60 * 1000), &mut leds);
+        assert_eq!(leds[0], effect.color_at(TimeOfDay::from_hms(18, 0, 0)));
+        assert!(leds[0].b < kelvin_to_rgb(6000).b);
+    }
+}
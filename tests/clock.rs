@@ -0,0 +1,156 @@
+mod common;
+
+mod tests {
+    use myrtio_light_composer::{
+        EffectId,
+        Instant,
+        IntentChannel,
+        LightChangeIntent,
+        LightStateIntent,
+        Renderer,
+        bounds::{RING_TURN, ring_distance, ring_position},
+        color::Rgb,
+        effect::{ClockEffect, ClockStyle, Effect},
+        time_of_day::{DayClock, TimeOfDay},
+    };
+
+    use crate::common;
+
+    const RED: Rgb = Rgb { r: 255, g: 0, b: 0 };
+    const GREEN: Rgb = Rgb { r: 0, g: 255, b: 0 };
+    const BLUE: Rgb = Rgb { r: 0, g: 0, b: 255 };
+    const BLACK: Rgb = Rgb { r: 0, g: 0, b: 0 };
+    const YELLOW: Rgb = Rgb {
+        r: 255,
+        g: 255,
+        b: 0,
+    };
+
+    const STYLE: ClockStyle = ClockStyle {
+        hour: RED,
+        minute: GREEN,
+        second: None,
+        background: BLACK,
+        top_offset: 0,
+        reversed: false,
+    };
+
+    #[test]
+    fn test_ring_position() {
+        assert_eq!(ring_position(12, 0, 0, false), 0);
+        assert_eq!(ring_position(12, 0, RING_TURN / 4, false), 3 << 8);
+        assert_eq!(ring_position(12, 5, RING_TURN / 4, false), 8 << 8);
+        assert_eq!(ring_position(12, 5, RING_TURN / 2, false), 11 << 8);
+        assert_eq!(ring_position(12, 5, RING_TURN * 3 / 4, false), 2 << 8);
+        assert_eq!(ring_position(12, 0, RING_TURN / 4, true), 9 << 8);
+    }
+
+    #[test]
+    fn test_ring_distance_wraps() {
+        assert_eq!(ring_distance(12, 0, 11 << 8), 1 << 8);
+        assert_eq!(ring_distance(12, 1 << 8, 3 << 8), 2 << 8);
+        assert_eq!(ring_distance(12, 0, 6 << 8), 6 << 8);
+    }
+
+    fn render_at(style: ClockStyle, time: TimeOfDay) -> [Rgb; 12] {
+        let mut effect = ClockEffect::new().with_style(style);
+        effect.set_clock(DayClock::new(time, Instant::from_millis(0)));
+        let mut leds = [BLACK; 12];
+        effect.render(Instant::from_millis(0), &mut leds);
+        leds
+    }
+
+    #[test]
+    fn test_clock_hands() {
+        let leds = render_at(STYLE, TimeOfDay::from_hms(3, 0, 0));
+        assert_eq!(leds[0], GREEN);
+        assert_eq!(leds[3], RED);
+        // Soft edges
+        assert!(leds[4].r > 0 && leds[4].r < 255);
+        assert_eq!(leds[6], BLACK);
+    }
+
+    #[test]
+    fn test_clock_top_offset() {
+        let style = ClockStyle {
+            top_offset: 6,
+            ..STYLE
+        };
+        let leds = render_at(style, TimeOfDay::from_hms(3, 0, 0));
+        assert_eq!(leds[6], GREEN);
+        assert_eq!(leds[9], RED);
+    }
+
+    #[test]
+    fn test_clock_second_sweep() {
+        let style = ClockStyle {
+            second: Some(BLUE),
+            ..STYLE
+        };
+        // Half a second past 6:00:15 the second hand is between LEDs 3 and 4
+        let mut effect = ClockEffect::new().with_style(style);
+        effect.set_clock(DayClock::new(
+            TimeOfDay::from_hms(6, 0, 15),
+            Instant::from_millis(0),
+        ));
+        let mut leds = [BLACK; 12];
+        effect.render(Instant::from_millis(2_500), &mut leds);
+        assert!(leds[3].b > 0 && leds[3].b < 255);
+        assert!(leds[4].b > 0 && leds[4].b < 255);
+    }
+
+    #[test]
+    fn test_style_intent_keeps_hand_colors() {
+        let intents = IntentChannel::<8>::new();
+        let config = common::config(EffectId::Clock, 12, YELLOW);
+        let mut renderer = Renderer::<12, 8>::new(intents.receiver(), &config);
+        let sender = intents.sender();
+        let _ = sender
+            .try_send(LightChangeIntent::TimeOfDay(TimeOfDay::from_hms(3, 0, 0)));
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            power: Some(true),
+            secondary_color: Some(YELLOW),
+            ..Default::default()
+        }));
+        let mut t = 0;
+        let mut render = |intent: Option<LightChangeIntent>| {
+            if let Some(intent) = intent {
+                let _ = sender.try_send(intent);
+            }
+            let mut frame = [BLACK; 12];
+            for _ in 0..10 {
+                frame.copy_from_slice(renderer.render(Instant::from_millis(t)));
+                t += 10;
+            }
+            frame
+        };
+        render(None);
+
+        // The style wins over the slot colors set before it
+        let frame = render(Some(LightChangeIntent::ClockStyle(STYLE)));
+        assert_eq!(frame[3], RED);
+        assert!(frame[0].g > 200 && frame[0].r == 0);
+
+        // Also after switching effects
+        let switch = |effect_id| {
+            Some(LightChangeIntent::State(LightStateIntent {
+                effect_id: Some(effect_id),
+                ..Default::default()
+            }))
+        };
+        render(switch(EffectId::Static));
+        let frame = render(switch(EffectId::Clock));
+        assert_eq!(frame[3], RED);
+        assert!(frame[0].g > 200 && frame[0].r == 0);
+
+        // Slot colors set after the style replace its colors
+        let frame = render(Some(LightChangeIntent::State(LightStateIntent {
+            color: Some(BLUE),
+            ..Default::default()
+        })));
+        assert_eq!(frame[3], BLUE);
+        render(switch(EffectId::Static));
+        let frame = render(switch(EffectId::Clock));
+        assert_eq!(frame[3], BLUE);
+    }
+}
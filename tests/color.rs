@@ -2,7 +2,12 @@ mod tests {
     use myrtio_light_composer::color::{
         Rgb,
         blend_colors,
+        kelvin_to_mired,
         kelvin_to_rgb,
+        kelvin_to_rgb_fixed,
+        kelvin_to_rgb_float,
+        mired_to_kelvin,
+        mired_to_rgb,
         mirror_half,
     };
 
@@ -75,4 +80,42 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_kelvin_to_rgb_fixed_accuracy() {
+        let channel_error = |a: u8, b: u8| a.abs_diff(b);
+        let mut exact = 0;
+        for kelvin in 0..=u16::MAX {
+            let float = kelvin_to_rgb_float(kelvin);
+            let fixed = kelvin_to_rgb_fixed(kelvin);
+            let error = channel_error(float.r, fixed.r)
+                .max(channel_error(float.g, fixed.g))
+                .max(channel_error(float.b, fixed.b));
+            assert!(error <= 1, "{kelvin}K: {float:?} != {fixed:?}");
+            if error == 0 {
+                exact += 1;
+            }
+        }
+        // Off by one only where the float result is close to a step
+        assert!(exact > 60_000, "{exact}");
+    }
+
+    #[test]
+    fn test_kelvin_to_rgb_fixed_ends() {
+        assert_eq!(kelvin_to_rgb_fixed(1000), kelvin_to_rgb_float(1000));
+        assert_eq!(kelvin_to_rgb_fixed(6600), kelvin_to_rgb_float(6600));
+        assert_eq!(kelvin_to_rgb_fixed(40000), kelvin_to_rgb_float(40000));
+        assert_eq!(kelvin_to_rgb_fixed(0), kelvin_to_rgb_fixed(1000));
+        assert_eq!(kelvin_to_rgb_fixed(u16::MAX), kelvin_to_rgb_fixed(40000));
+    }
+
+    #[test]
+    fn test_mired() {
+        assert_eq!(mired_to_kelvin(370), 2702);
+        assert_eq!(mired_to_kelvin(153), 6535);
+        assert_eq!(mired_to_kelvin(0), u16::MAX);
+        assert_eq!(kelvin_to_mired(2700), 370);
+        assert_eq!(kelvin_to_mired(0), 62_500);
+        assert_eq!(mired_to_rgb(250), kelvin_to_rgb(4000));
+    }
 }
@@ -0,0 +1,139 @@
+mod tests {
+    use myrtio_light_composer::{
+        ColorOrder,
+        ColorTemperature,
+        OutputDriver,
+        RgbOutput,
+        RgbcctOutput,
+        RgbcctOutputDriver,
+        RgbwOutput,
+        RgbwOutputDriver,
+        color::{
+            CctConfig,
+            CctWhiteOrder,
+            Rgb,
+            Rgbcct,
+            Rgbw,
+            WhiteExtraction,
+            WhitePosition,
+            rgbcct,
+            rgbw,
+        },
+    };
+
+    const COLOR: Rgb = Rgb { r: 1, g: 2, b: 3 };
+
+    #[test]
+    fn test_all_orders() {
+        let cases = [
+            (ColorOrder::Rgb, Rgb::new(1, 2, 3)),
+            (ColorOrder::Rbg, Rgb::new(1, 3, 2)),
+            (ColorOrder::Grb, Rgb::new(2, 1, 3)),
+            (ColorOrder::Gbr, Rgb::new(2, 3, 1)),
+            (ColorOrder::Brg, Rgb::new(3, 1, 2)),
+            (ColorOrder::Bgr, Rgb::new(3, 2, 1)),
+        ];
+        for (order, expected) in cases {
+            assert_eq!(order.apply(COLOR), expected, "{order:?}");
+        }
+    }
+
+    #[test]
+    fn test_white_position() {
+        let pixel = rgbw(COLOR, 4);
+        let last = ColorOrder::Grb.apply_rgbw(pixel, WhitePosition::Last);
+        assert_eq!(last, rgbw(Rgb::new(2, 1, 3), 4));
+
+        let first = ColorOrder::Grb.apply_rgbw(pixel, WhitePosition::First);
+        assert_eq!(first, rgbw(Rgb::new(4, 2, 1), 3));
+    }
+
+    #[derive(Default)]
+    struct Recorder {
+        frame: Vec<Rgb>,
+        frame_rgbw: Vec<Rgbw>,
+        frame_rgbcct: Vec<Rgbcct>,
+    }
+
+    impl OutputDriver for Recorder {
+        fn write(&mut self, colors: &[Rgb]) {
+            self.frame = colors.to_vec();
+        }
+    }
+
+    impl RgbwOutputDriver for Recorder {
+        fn write_rgbw(&mut self, colors: &[Rgbw]) {
+            self.frame_rgbw = colors.to_vec();
+        }
+    }
+
+    impl RgbcctOutputDriver for Recorder {
+        fn write_rgbcct(&mut self, colors: &[Rgbcct]) {
+            self.frame_rgbcct = colors.to_vec();
+        }
+    }
+
+    #[test]
+    fn test_rgb_output_order() {
+        let mut output =
+            RgbOutput::<Recorder, 2>::new(Recorder::default(), ColorOrder::Grb);
+        let frame = [Rgb::new(255, 100, 0), Rgb::new(1, 2, 3)];
+
+        // Reordered once per write, not on top of the previous frame
+        for _ in 0..2 {
+            output.write(&frame);
+            assert_eq!(
+                output.driver().frame,
+                [Rgb::new(100, 255, 0), Rgb::new(2, 1, 3)]
+            );
+        }
+    }
+
+    #[test]
+    fn test_rgbw_output_order() {
+        let mut output = RgbwOutput::<Recorder, 2>::new(
+            Recorder::default(),
+            WhiteExtraction::MinSubtract,
+        );
+        output.set_order(ColorOrder::Brg, WhitePosition::First);
+        output.write(&[Rgb::new(50, 20, 30)]);
+        assert_eq!(output.driver().frame_rgbw, [rgbw(Rgb::new(20, 10, 30), 0)]);
+    }
+
+    #[test]
+    fn test_rgbw_output_white_led_order() {
+        let mut output = RgbwOutput::<Recorder, 2>::new(
+            Recorder::default(),
+            WhiteExtraction::WhiteLed(Rgb::new(255, 255, 255)),
+        );
+        output.set_order(ColorOrder::Grb, WhitePosition::Last);
+        output.write(&[Rgb::new(200, 100, 50)]);
+
+        // White is extracted from the RGB color before reordering
+        assert_eq!(output.driver().frame_rgbw, [rgbw(Rgb::new(50, 150, 0), 50)]);
+    }
+
+    #[test]
+    fn test_rgbcct_output_order() {
+        let mut output = RgbcctOutput::<Recorder, 2>::new(
+            Recorder::default(),
+            CctConfig::default(),
+        );
+        output.set_order(ColorOrder::Bgr, CctWhiteOrder::WarmCold);
+        output.write(&[Rgb::new(1, 2, 3)]);
+        assert_eq!(
+            output.driver().frame_rgbcct,
+            [rgbcct(Rgb::new(3, 2, 1), 0, 0)]
+        );
+
+        output.set_color_temperature(Some(ColorTemperature {
+            kelvin: 2_700,
+            amount: 255,
+        }));
+        output.write(&[Rgb::new(255, 255, 255)]);
+
+        // The warm white is sent first, in the cold slot
+        let pixel = output.driver().frame_rgbcct[0];
+        assert_eq!((pixel.a.cold, pixel.a.warm), (255, 0));
+    }
+}
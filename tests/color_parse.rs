@@ -0,0 +1,83 @@
+mod tests {
+    use myrtio_light_composer::color::{
+        ColorParseError,
+        ParsedColor,
+        Rgb,
+        format_hex,
+        kelvin_to_rgb,
+        parse_color,
+        parse_rgb,
+    };
+
+    const ORANGE: Rgb = Rgb {
+        r: 255,
+        g: 136,
+        b: 0,
+    };
+
+    fn rgb(input: &str) -> Rgb {
+        match parse_color(input) {
+            Ok(ParsedColor::Rgb(color)) => color,
+            other => panic!("{input}: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(rgb("#ff8800"), ORANGE);
+        assert_eq!(rgb("FF8800"), ORANGE);
+        assert_eq!(rgb("  #f80 "), ORANGE);
+        assert_eq!(parse_color("#ff88"), Err(ColorParseError::InvalidHex));
+        assert_eq!(parse_color("#ff88zz"), Err(ColorParseError::InvalidHex));
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(rgb("orange"), Rgb::new(255, 165, 0));
+        assert_eq!(rgb("RebeccaPurple"), Rgb::new(0x66, 0x33, 0x99));
+        assert_eq!(rgb("aliceblue"), Rgb::new(0xF0, 0xF8, 0xFF));
+        assert_eq!(rgb("yellowgreen"), Rgb::new(0x9A, 0xCD, 0x32));
+        assert_eq!(parse_color("blurple"), Err(ColorParseError::UnknownName));
+        assert_eq!(parse_color("   "), Err(ColorParseError::Empty));
+    }
+
+    #[test]
+    fn test_functions() {
+        assert_eq!(rgb("rgb(255,136,0)"), ORANGE);
+        assert_eq!(rgb("RGB( 255, 136, 0 )"), ORANGE);
+        assert_eq!(rgb("hsv(0, 100%, 100%)"), Rgb::new(255, 0, 0));
+        assert_eq!(rgb("hsv(30,0%,100%)"), Rgb::new(255, 255, 255));
+        assert_eq!(rgb("hsv(360, 100, 0)"), Rgb::new(0, 0, 0));
+
+        let orange = rgb("hsv(30,100%,100%)");
+        assert_eq!(orange.r, 255);
+        assert!(orange.g > 100 && orange.g < 160 && orange.b == 0);
+
+        let error = |input| parse_color(input).unwrap_err();
+        assert_eq!(error("rgb(255,136)"), ColorParseError::InvalidSyntax);
+        assert_eq!(error("rgb(255,136,0,0)"), ColorParseError::InvalidSyntax);
+        assert_eq!(error("rgb(255,136,0"), ColorParseError::InvalidSyntax);
+        assert_eq!(error("rgb(256,136,0)"), ColorParseError::OutOfRange);
+        assert_eq!(error("rgb(-1,136,0)"), ColorParseError::InvalidNumber);
+        assert_eq!(error("hsv(30,101%,100%)"), ColorParseError::OutOfRange);
+    }
+
+    #[test]
+    fn test_kelvin() {
+        assert_eq!(parse_color("2700K"), Ok(ParsedColor::Kelvin(2700)));
+        assert_eq!(" 6500k".parse(), Ok(ParsedColor::Kelvin(6500)));
+        assert_eq!(parse_color("999K"), Err(ColorParseError::OutOfRange));
+        assert_eq!(parse_color("70000K"), Err(ColorParseError::OutOfRange));
+        assert_eq!(parse_rgb("2700K"), Ok(kelvin_to_rgb(2700)));
+    }
+
+    #[test]
+    fn test_format_hex() {
+        assert_eq!(format_hex(ORANGE), "#ff8800");
+        assert_eq!(format_hex(Rgb::new(1, 2, 3)), "#010203");
+
+        for color in [ORANGE, Rgb::new(0, 0, 0), Rgb::new(18, 52, 86)] {
+            assert_eq!(parse_rgb(&format_hex(color)), Ok(color));
+        }
+    }
+}
@@ -0,0 +1,191 @@
+mod common;
+
+mod tests {
+    use myrtio_light_composer::{
+        ColorBlending,
+        Duration,
+        EffectId,
+        Instant,
+        IntentChannel,
+        LightChangeIntent,
+        LightStateIntent,
+        Operation,
+        OperationStack,
+        Renderer,
+        color::{COLOR_SLOTS, ColorSlot, GradientDirection, Rgb},
+        effect::{GarlandMode, GarlandStyle},
+        time_of_day::TimeOfDay,
+    };
+
+    use crate::common;
+
+    const RED: Rgb = Rgb { r: 255, g: 0, b: 0 };
+    const GREEN: Rgb = Rgb { r: 0, g: 255, b: 0 };
+    const BLUE: Rgb = Rgb { r: 0, g: 0, b: 255 };
+    const BLACK: Rgb = Rgb { r: 0, g: 0, b: 0 };
+
+    #[test]
+    fn test_slot_index_round_trip() {
+        for (index, slot) in ColorSlot::ALL.into_iter().enumerate() {
+            assert_eq!(slot.index(), index);
+            assert_eq!(ColorSlot::from_index(index), Some(slot));
+        }
+        assert_eq!(ColorSlot::from_index(COLOR_SLOTS), None);
+    }
+
+    #[test]
+    fn test_intent_slot_color() {
+        let intent = LightStateIntent {
+            color: Some(RED),
+            tertiary_color: Some(BLUE),
+            ..Default::default()
+        };
+        assert_eq!(intent.slot_color(ColorSlot::Primary), Some(RED));
+        assert_eq!(intent.slot_color(ColorSlot::Secondary), None);
+        assert_eq!(intent.slot_color(ColorSlot::Tertiary), Some(BLUE));
+    }
+
+    #[test]
+    fn test_push_color_uses_primary_slot() {
+        let mut stack = OperationStack::<4>::new();
+        let _ = stack.push_color(RED);
+        let _ = stack.push_slot_color(ColorSlot::Secondary, GREEN, None);
+        assert!(matches!(
+            stack.current(),
+            Some(Operation::SetColor(ColorSlot::Primary, RED, None))
+        ));
+        let _ = stack.pop();
+        assert!(matches!(
+            stack.pop(),
+            Some(Operation::SetColor(ColorSlot::Secondary, GREEN, None))
+        ));
+    }
+
+    #[test]
+    fn test_slot_colors_survive_effect_switch() {
+        let intents = IntentChannel::<8>::new();
+        let config = common::config(EffectId::Garland, 3, RED);
+        let mut renderer = Renderer::<3, 8>::new(intents.receiver(), &config);
+        let sender = intents.sender();
+        let _ = sender.try_send(LightChangeIntent::GarlandStyle(GarlandStyle {
+            mode: GarlandMode::Fade,
+            group_size: 1,
+            gap: 0,
+            step: Duration::from_secs(3_600),
+            twinkle: false,
+        }));
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            power: Some(true),
+            secondary_color: Some(GREEN),
+            tertiary_color: Some(BLUE),
+            ..Default::default()
+        }));
+
+        let mut frame = [BLACK; 3];
+        let mut t = 0;
+        let mut render = |renderer: &mut Renderer<3, 8>, frame: &mut [Rgb; 3]| {
+            for _ in 0..10 {
+                frame.copy_from_slice(renderer.render(Instant::from_millis(t)));
+                t += 100;
+            }
+        };
+        render(&mut renderer, &mut frame);
+        assert_eq!(frame, [RED, GREEN, BLUE]);
+
+        // Single-color effects only read the primary slot
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            effect_id: Some(EffectId::Static),
+            ..Default::default()
+        }));
+        render(&mut renderer, &mut frame);
+        assert_eq!(frame, [RED, RED, RED]);
+
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            effect_id: Some(EffectId::Garland),
+            ..Default::default()
+        }));
+        render(&mut renderer, &mut frame);
+        assert_eq!(frame, [RED, GREEN, BLUE]);
+    }
+
+    #[test]
+    fn test_slots_color_multi_color_effects() {
+        let intents = IntentChannel::<8>::new();
+        let config = common::config(EffectId::StrobeAlternate, 4, RED);
+        let mut renderer = Renderer::<4, 8>::new(intents.receiver(), &config);
+        let sender = intents.sender();
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            power: Some(true),
+            secondary_color: Some(GREEN),
+            tertiary_color: Some(BLUE),
+            ..Default::default()
+        }));
+        for t in 0..10 {
+            renderer.render(Instant::from_millis(t));
+        }
+        assert_eq!(
+            renderer.render(Instant::from_millis(1_000)),
+            [RED, BLACK, RED, BLACK]
+        );
+        assert_eq!(
+            renderer.render(Instant::from_millis(1_500)),
+            [BLACK, GREEN, BLACK, GREEN]
+        );
+
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            effect_id: Some(EffectId::Gradient),
+            ..Default::default()
+        }));
+        let mut frame = [BLACK; 4];
+        for t in 0..10 {
+            frame.copy_from_slice(renderer.render(Instant::from_millis(2_000 + t)));
+        }
+        assert_eq!(frame[0], RED);
+        assert_eq!(frame[3], BLUE);
+        assert!(frame[1].g > frame[1].b && frame[2].b > frame[2].r);
+    }
+
+    #[test]
+    fn test_multi_color_effects_transition_slots() {
+        // Effect, LED showing the primary slot, time of the color change
+        let cases = [
+            (EffectId::StrobeHalves, 0, 1_500),
+            (EffectId::Clock, 3, 1_000),
+            (EffectId::Gradient, 0, 1_000),
+        ];
+        for (effect, led, start) in cases {
+            let intents = IntentChannel::<8>::new();
+            let mut config = common::config(effect, 12, RED);
+            config.timings.color_change = Duration::from_millis(1_000);
+            let mut renderer = Renderer::<12, 8>::new(intents.receiver(), &config);
+            let sender = intents.sender();
+            let _ = sender.try_send(LightChangeIntent::TimeOfDay(
+                TimeOfDay::from_hms(3, 0, 0),
+            ));
+            let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+                power: Some(true),
+                ..Default::default()
+            }));
+            for t in 0..10 {
+                renderer.render(Instant::from_millis(t));
+            }
+
+            // Hue goes forward from red through green to blue
+            let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+                color: Some(BLUE),
+                color_blending: Some(ColorBlending::Hsv(GradientDirection::Forward)),
+                ..Default::default()
+            }));
+            for t in start..start + 3 {
+                renderer.render(Instant::from_millis(t));
+            }
+            let halfway = renderer.render(Instant::from_millis(start + 500))[led];
+            assert!(
+                halfway.g > halfway.r && halfway.g > halfway.b,
+                "{effect:?}: {halfway:?}"
+            );
+            let done = renderer.render(Instant::from_millis(start + 1_500))[led];
+            assert_eq!(done, BLUE, "{effect:?}");
+        }
+    }
+}
@@ -0,0 +1,41 @@
+//! Shared fixtures for the integration tests
+
+use myrtio_light_composer::{
+    Duration,
+    EffectId,
+    FilterProcessorConfig,
+    LightEngineConfig,
+    TransitionTimings,
+    bounds::RenderingBounds,
+    color::Rgb,
+    filter::BrightnessFilterConfig,
+};
+
+/// Engine config for `leds` LEDs with instant transitions, full brightness
+/// and no color correction
+pub(crate) fn config(effect: EffectId, leds: u8, color: Rgb) -> LightEngineConfig {
+    LightEngineConfig {
+        effect,
+        bounds: RenderingBounds {
+            start: 0,
+            end: leds,
+        },
+        filters: FilterProcessorConfig {
+            brightness: BrightnessFilterConfig {
+                min_brightness: 0,
+                scale: 255,
+                adjust: None,
+            },
+            color_correction: Rgb::new(255, 255, 255),
+            gamma: None,
+        },
+        timings: TransitionTimings {
+            fade_out: Duration::from_millis(0),
+            fade_in: Duration::from_millis(0),
+            color_change: Duration::from_millis(0),
+            brightness: Duration::from_millis(0),
+        },
+        brightness: 255,
+        color,
+    }
+}
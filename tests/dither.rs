@@ -0,0 +1,162 @@
+mod common;
+
+mod tests {
+    use myrtio_light_composer::{
+        Duration,
+        EffectId,
+        Instant,
+        IntentChannel,
+        LightChangeIntent,
+        LightEngineConfig,
+        LightStateIntent,
+        Renderer,
+        color::Rgb,
+        math8::U8Adjuster,
+    };
+
+    use crate::common;
+
+    const WHITE: Rgb = Rgb {
+        r: 255,
+        g: 255,
+        b: 255,
+    };
+
+    fn config() -> LightEngineConfig {
+        let mut config = common::config(EffectId::Static, 2, WHITE);
+        config.timings.brightness = Duration::from_millis(1_000);
+        config.brightness = 3;
+        config
+    }
+
+    /// Red channel of the first LED over 256 frames, halfway into a fade
+    /// from off to brightness 3
+    fn fade_levels(renderer: &mut Renderer<'_, 2, 8>) -> Vec<u8> {
+        renderer.render(Instant::from_millis(0));
+        (0..256)
+            .map(|_| renderer.render(Instant::from_millis(500))[0].r)
+            .collect()
+    }
+
+    fn power_on(intents: &IntentChannel<8>) {
+        let _ =
+            intents
+                .sender()
+                .try_send(LightChangeIntent::State(LightStateIntent {
+                    power: Some(true),
+                    ..Default::default()
+                }));
+    }
+
+    #[test]
+    fn test_fade_is_dithered() {
+        let intents = IntentChannel::<8>::new();
+        let mut renderer = Renderer::<2, 8>::new(intents.receiver(), &config());
+        power_on(&intents);
+
+        // Halfway between levels 1 and 2, both show equally often
+        let levels = fade_levels(&mut renderer);
+        assert!(levels.iter().all(|level| *level == 1 || *level == 2));
+        let total: u32 = levels.iter().map(|level| u32::from(*level)).sum();
+        assert!((380..=388).contains(&total));
+        assert!(levels.contains(&1) && levels.contains(&2));
+    }
+
+    #[test]
+    fn test_dithering_can_be_disabled() {
+        let intents = IntentChannel::<8>::new();
+        let mut renderer = Renderer::<2, 8>::new(intents.receiver(), &config())
+            .with_dithering(false);
+        power_on(&intents);
+
+        let levels = fade_levels(&mut renderer);
+        assert!(levels.iter().all(|level| *level == 1));
+    }
+
+    #[test]
+    fn test_full_and_zero_brightness_are_exact() {
+        let intents = IntentChannel::<8>::new();
+        let mut renderer = Renderer::<2, 8>::new(intents.receiver(), &config());
+        for t in 0..4 {
+            assert_eq!(
+                renderer.render(Instant::from_millis(t)),
+                [Rgb::default(); 2]
+            );
+        }
+
+        let _ =
+            intents
+                .sender()
+                .try_send(LightChangeIntent::State(LightStateIntent {
+                    power: Some(true),
+                    brightness: Some(255),
+                    ..Default::default()
+                }));
+        for t in 0..10 {
+            renderer.render(Instant::from_millis(t * 1_000));
+        }
+        for t in 0..4 {
+            assert_eq!(
+                renderer.render(Instant::from_millis(20_000 + t)),
+                [WHITE; 2]
+            );
+        }
+    }
+
+    #[test]
+    fn test_color_correction_keeps_precision_while_fading() {
+        let mut config = config();
+        config.color = Rgb::new(0, 3, 0);
+        config.brightness = 200;
+        config.filters.color_correction = Rgb::new(255, 128, 255);
+        let intents = IntentChannel::<8>::new();
+        let mut renderer = Renderer::<2, 8>::new(intents.receiver(), &config);
+        power_on(&intents);
+
+        // The corrected green of 1.5 levels is kept until the output, so
+        // late in the fade it still shows as 1 and 2 in turns, not as a
+        // truncated 1 dimmed below it
+        renderer.render(Instant::from_millis(0));
+        let levels: Vec<u8> = (0..256)
+            .map(|_| renderer.render(Instant::from_millis(990))[0].g)
+            .collect();
+        let total: u32 = levels.iter().map(|level| u32::from(*level)).sum();
+        assert!((290..=310).contains(&total));
+        assert!(levels.contains(&2));
+    }
+
+    /// Red channel of the first LED over 256 frames after the fade ended
+    fn steady_levels(renderer: &mut Renderer<'_, 2, 8>) -> Vec<u8> {
+        for t in 0..4 {
+            renderer.render(Instant::from_millis(t * 1_000));
+        }
+        (0..256)
+            .map(|_| renderer.render(Instant::from_millis(5_000))[0].r)
+            .collect()
+    }
+
+    #[test]
+    fn test_steady_brightness_does_not_flicker() {
+        let intents = IntentChannel::<8>::new();
+        let mut renderer = Renderer::<2, 8>::new(intents.receiver(), &config());
+        power_on(&intents);
+
+        let levels = steady_levels(&mut renderer);
+        assert!(levels.iter().all(|level| *level == 3));
+    }
+
+    #[test]
+    fn test_adjuster_keeps_steady_levels_exact() {
+        const IDENTITY: U8Adjuster = |value| value;
+
+        let intents = IntentChannel::<8>::new();
+        let mut renderer = Renderer::<2, 8>::new(intents.receiver(), &config());
+        let _ = intents
+            .sender()
+            .try_send(LightChangeIntent::Adjuster(Some(IDENTITY)));
+        power_on(&intents);
+
+        let levels = steady_levels(&mut renderer);
+        assert!(levels.iter().all(|level| *level == 3));
+    }
+}
@@ -2,33 +2,29 @@ mod tests {
     use myrtio_light_composer::EffectId;
 
     #[test]
-    fn test_effect_id_parse_aurora() {
-        assert_eq!(EffectId::parse_from_str("aurora"), Some(EffectId::Aurora));
+    fn test_effect_id_parse_neon() {
+        assert_eq!(EffectId::parse_from_str("neon"), Some(EffectId::Neon));
     }
 
     #[test]
-    fn test_effect_id_from_raw_aurora() {
-        // Aurora is appended at the end; current ID is 7.
-        assert_eq!(EffectId::from_raw(6), Some(EffectId::Aurora));
+    fn test_effect_id_from_raw_neon() {
+        assert_eq!(EffectId::from_raw(6), Some(EffectId::Neon));
     }
 
     #[test]
-    fn test_effect_id_parse_lava_lamp() {
-        assert_eq!(
-            EffectId::parse_from_str("lava_lamp"),
-            Some(EffectId::LavaLamp)
-        );
+    fn test_effect_id_parse_rest() {
+        assert_eq!(EffectId::parse_from_str("rest"), Some(EffectId::Rest));
     }
 
     #[test]
-    fn test_effect_id_from_raw_lava_lamp() {
-        // LavaLamp is ID 7.
-        assert_eq!(EffectId::from_raw(7), Some(EffectId::LavaLamp));
+    fn test_effect_id_from_raw_rest() {
+        // Rest (lava lamp palette) is ID 7.
+        assert_eq!(EffectId::from_raw(7), Some(EffectId::Rest));
     }
 
     #[test]
-    fn test_effect_id_as_str_lava_lamp() {
-        assert_eq!(EffectId::LavaLamp.as_str(), "lava_lamp");
+    fn test_effect_id_as_str_rest() {
+        assert_eq!(EffectId::Rest.as_str(), "rest");
     }
 
     #[test]
@@ -45,4 +41,21 @@ mod tests {
     fn test_effect_id_as_str_sunset() {
         assert_eq!(EffectId::Sunset.as_str(), "sunset");
     }
+
+    #[test]
+    fn test_effect_id_circadian() {
+        assert_eq!(EffectId::from_raw(9), Some(EffectId::Circadian));
+        assert_eq!(
+            EffectId::parse_from_str("circadian"),
+            Some(EffectId::Circadian)
+        );
+        assert_eq!(EffectId::Circadian.as_str(), "circadian");
+    }
+
+    #[test]
+    fn test_effect_id_plasma() {
+        assert_eq!(EffectId::from_raw(10), Some(EffectId::Plasma));
+        assert_eq!(EffectId::parse_from_str("plasma"), Some(EffectId::Plasma));
+        assert_eq!(EffectId::Plasma.as_str(), "plasma");
+    }
 }
@@ -0,0 +1,118 @@
+mod tests {
+    use myrtio_light_composer::{
+        Duration,
+        Instant,
+        color::Rgb,
+        effect::{
+            Effect,
+            FlowEffect,
+            FlowPalette,
+            FlowVariant,
+            MAX_FLOW_PALETTE_COLORS,
+        },
+    };
+
+    const RED: Rgb = Rgb { r: 255, g: 0, b: 0 };
+    const GREEN: Rgb = Rgb { r: 0, g: 255, b: 0 };
+    const BLUE: Rgb = Rgb { r: 0, g: 0, b: 255 };
+    const BLACK: Rgb = Rgb { r: 0, g: 0, b: 0 };
+
+    #[test]
+    fn test_palette_validation() {
+        assert!(FlowPalette::new(&[]).is_none());
+        assert!(FlowPalette::new(&[RED; MAX_FLOW_PALETTE_COLORS + 1]).is_none());
+
+        let palette = FlowPalette::new(&[RED; MAX_FLOW_PALETTE_COLORS]).unwrap();
+        assert_eq!(palette.palette().len(), MAX_FLOW_PALETTE_COLORS);
+    }
+
+    #[test]
+    fn test_layer_periods() {
+        let palette = FlowPalette::new(&[RED, GREEN]).unwrap().with_layer_periods(
+            Duration::from_millis(1_000),
+            Duration::from_millis(0),
+            Duration::from_millis(3_000),
+        );
+        assert_eq!(
+            palette.layer_periods(),
+            [
+                Duration::from_millis(1_000),
+                Duration::from_millis(1),
+                Duration::from_millis(3_000),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_user_palette_colors() {
+        let palette = FlowPalette::new(&[RED]).unwrap();
+        let mut effect = FlowEffect::new(FlowVariant::Neon).with_palette(palette);
+        let mut leds = [BLACK; 20];
+        effect.render(Instant::from_millis(1_000), &mut leds);
+        // Single-color palette only varies in brightness
+        assert!(
+            leds.iter()
+                .all(|led| led.r >= 190 && led.g == 0 && led.b == 0)
+        );
+    }
+
+    #[test]
+    fn test_palette_crossfade() {
+        let red = FlowPalette::new(&[RED]).unwrap();
+        let green = FlowPalette::new(&[GREEN]).unwrap();
+        let mut effect = FlowEffect::new(FlowVariant::Neon).with_palette(red);
+        let mut leds = [BLACK; 20];
+
+        effect.set_palette(
+            Some(green.clone()),
+            Duration::from_millis(1_000),
+            Instant::from_millis(0),
+        );
+        assert!(effect.is_crossfading());
+        // Color changes are not held back by the crossfade
+        assert!(!effect.is_transitioning());
+
+        effect.render(Instant::from_millis(500), &mut leds);
+        assert!(leds.iter().all(|led| led.r > 0 && led.g > 0));
+
+        effect.render(Instant::from_millis(1_000), &mut leds);
+        assert!(!effect.is_crossfading());
+        assert!(leds.iter().all(|led| led.r == 0 && led.g > 0));
+        assert_eq!(effect.palette(), &green);
+    }
+
+    #[test]
+    fn test_reset_to_builtin_palette() {
+        let mut effect = FlowEffect::new(FlowVariant::Sunset)
+            .with_palette(FlowPalette::new(&[RED]).unwrap());
+        effect.set_palette(None, Duration::from_millis(0), Instant::from_millis(0));
+        assert!(!effect.is_crossfading());
+        assert_eq!(
+            effect.palette(),
+            &FlowPalette::from_variant(FlowVariant::Sunset)
+        );
+    }
+
+    #[test]
+    fn test_palette_change_during_crossfade() {
+        let red = FlowPalette::new(&[RED]).unwrap();
+        let green = FlowPalette::new(&[GREEN]).unwrap();
+        let blue = FlowPalette::new(&[BLUE]).unwrap();
+        let mut effect = FlowEffect::new(FlowVariant::Neon).with_palette(red);
+        let second = Duration::from_millis(1_000);
+        effect.set_palette(Some(green), second, Instant::from_millis(0));
+
+        let mut before = [BLACK; 20];
+        effect.render(Instant::from_millis(500), &mut before);
+        effect.set_palette(Some(blue), second, Instant::from_millis(500));
+        let mut after = [BLACK; 20];
+        effect.render(Instant::from_millis(500), &mut after);
+
+        // Continues from the red/green mix instead of jumping to green
+        for (before, after) in before.iter().zip(&after) {
+            assert!(before.r.abs_diff(after.r) <= 4, "{before:?} {after:?}");
+            assert!(before.g.abs_diff(after.g) <= 4, "{before:?} {after:?}");
+            assert_eq!(after.b, 0);
+        }
+    }
+}
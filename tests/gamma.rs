@@ -0,0 +1,100 @@
+mod common;
+
+mod tests {
+    use myrtio_light_composer::{
+        EffectId,
+        Gamma,
+        GammaTable,
+        Instant,
+        IntentChannel,
+        LightChangeIntent,
+        LightStateIntent,
+        Renderer,
+        color::Rgb,
+        ws2812_lut,
+    };
+
+    use crate::common;
+
+    static GAMMA: Gamma = Gamma::new(2.2, 2.0, 2.8);
+
+    const GRAY: Rgb = Rgb {
+        r: 128,
+        g: 128,
+        b: 128,
+    };
+
+    #[test]
+    fn test_linear_table() {
+        for value in 0..=255 {
+            assert_eq!(GammaTable::LINEAR.apply(value), value);
+        }
+    }
+
+    #[test]
+    fn test_generated_table_accuracy() {
+        let table = GammaTable::new(2.2);
+        for value in 0..=255u8 {
+            let expected = (f64::from(value) / 255.0).powf(2.2) * 255.0;
+            let actual = f64::from(table.apply(value));
+            assert!((actual - expected).abs() <= 0.5, "value {value}");
+        }
+        assert_eq!(table.apply(0), 0);
+        assert_eq!(table.apply(128), 56);
+        assert_eq!(table.apply(255), 255);
+    }
+
+    #[test]
+    #[should_panic(expected = "gamma must be in (0, 10]")]
+    fn test_rejects_infinite_gamma() {
+        let _ = GammaTable::new(f32::INFINITY);
+    }
+
+    #[test]
+    #[should_panic(expected = "gamma must be in (0, 10]")]
+    fn test_rejects_nan_gamma() {
+        let _ = GammaTable::new(f32::NAN);
+    }
+
+    #[test]
+    fn test_per_channel_tables() {
+        assert_eq!(
+            GAMMA.apply(GRAY),
+            Rgb {
+                r: 56,
+                g: 64,
+                b: 37
+            }
+        );
+        assert_eq!(Gamma::WS2812.r.apply(100), ws2812_lut(100));
+    }
+
+    #[test]
+    fn test_gamma_switch() {
+        let intents = IntentChannel::<8>::new();
+        let mut config = common::config(EffectId::Static, 2, GRAY);
+        config.filters.gamma = Some(&GAMMA);
+        let mut renderer = Renderer::<2, 8>::new(intents.receiver(), &config);
+        let sender = intents.sender();
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            power: Some(true),
+            ..Default::default()
+        }));
+        for t in 0..4 {
+            renderer.render(Instant::from_millis(t));
+        }
+        assert_eq!(
+            renderer.render(Instant::from_millis(4))[0],
+            GAMMA.apply(GRAY)
+        );
+
+        let _ = sender.try_send(LightChangeIntent::Gamma(false));
+        assert_eq!(renderer.render(Instant::from_millis(5))[0], GRAY);
+
+        let _ = sender.try_send(LightChangeIntent::Gamma(true));
+        assert_eq!(
+            renderer.render(Instant::from_millis(6))[0],
+            GAMMA.apply(GRAY)
+        );
+    }
+}
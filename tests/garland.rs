@@ -0,0 +1,188 @@
+mod common;
+
+mod tests {
+    use myrtio_light_composer::{
+        Duration,
+        EffectId,
+        Instant,
+        IntentChannel,
+        LightChangeIntent,
+        LightStateIntent,
+        Renderer,
+        color::Rgb,
+        effect::{
+            Effect,
+            GarlandColors,
+            GarlandEffect,
+            GarlandMode,
+            GarlandStyle,
+            MAX_GARLAND_COLORS,
+        },
+    };
+
+    use crate::common;
+
+    const RED: Rgb = Rgb { r: 255, g: 0, b: 0 };
+    const GREEN: Rgb = Rgb { r: 0, g: 255, b: 0 };
+    const BLUE: Rgb = Rgb { r: 0, g: 0, b: 255 };
+    const BLACK: Rgb = Rgb { r: 0, g: 0, b: 0 };
+    const WHITE: Rgb = Rgb {
+        r: 255,
+        g: 255,
+        b: 255,
+    };
+
+    const GROUPS: GarlandStyle = GarlandStyle {
+        mode: GarlandMode::Fade,
+        group_size: 2,
+        gap: 1,
+        step: Duration::from_millis(1_000),
+        twinkle: false,
+    };
+
+    #[test]
+    fn test_colors_validation() {
+        assert!(GarlandColors::new(&[RED]).is_none());
+        assert!(GarlandColors::new(&[RED; MAX_GARLAND_COLORS + 1]).is_none());
+        assert!(GarlandColors::new(&[RED, GREEN, BLUE]).is_some());
+    }
+
+    #[test]
+    fn test_alternating_groups() {
+        let colors = GarlandColors::new(&[RED, GREEN]).unwrap();
+        let mut effect = GarlandEffect::new(RED)
+            .with_colors(&colors)
+            .with_style(GROUPS);
+        let mut leds = [BLACK; 9];
+        effect.render(Instant::from_millis(0), &mut leds);
+        assert_eq!(
+            leds,
+            [RED, RED, BLACK, GREEN, GREEN, BLACK, RED, RED, BLACK]
+        );
+    }
+
+    #[test]
+    fn test_fade_crossfades_in_place() {
+        let colors = GarlandColors::new(&[RED, GREEN]).unwrap();
+        let mut effect = GarlandEffect::new(RED)
+            .with_colors(&colors)
+            .with_style(GROUPS);
+        let mut leds = [BLACK; 6];
+
+        effect.render(Instant::from_millis(500), &mut leds);
+        assert!(leds[0].r > 0 && leds[0].g > 0);
+        assert_eq!(leds[2], BLACK);
+
+        effect.render(Instant::from_millis(1_000), &mut leds);
+        assert_eq!(leds[0], GREEN);
+        assert_eq!(leds[3], RED);
+    }
+
+    #[test]
+    fn test_chase_moves_groups() {
+        let colors = GarlandColors::new(&[RED, GREEN]).unwrap();
+        let mut effect =
+            GarlandEffect::new(RED)
+                .with_colors(&colors)
+                .with_style(GarlandStyle {
+                    mode: GarlandMode::Chase,
+                    ..GROUPS
+                });
+        let mut leds = [BLACK; 6];
+
+        // One LED every third of a step
+        effect.render(Instant::from_millis(334), &mut leds);
+        assert_eq!(leds, [BLACK, RED, RED, BLACK, GREEN, GREEN]);
+
+        effect.render(Instant::from_millis(1_000), &mut leds);
+        assert_eq!(leds, [GREEN, GREEN, BLACK, RED, RED, BLACK]);
+    }
+
+    #[test]
+    fn test_twinkle_brightens_groups() {
+        let colors = GarlandColors::new(&[RED, BLUE]).unwrap();
+        let mut effect =
+            GarlandEffect::new(RED)
+                .with_colors(&colors)
+                .with_style(GarlandStyle {
+                    twinkle: true,
+                    step: Duration::from_secs(3_600),
+                    ..GROUPS
+                });
+        let mut leds = [BLACK; 60];
+        let mut twinkled = false;
+        for t in (0..10_000).step_by(20) {
+            effect.render(Instant::from_millis(t), &mut leds);
+            twinkled |= leds.iter().any(|led| led.g > 0);
+        }
+        assert!(twinkled);
+    }
+
+    #[test]
+    fn test_color_slots_set_colors() {
+        let intents = IntentChannel::<8>::new();
+        let config = common::config(EffectId::Garland, 6, RED);
+        let mut renderer = Renderer::<6, 8>::new(intents.receiver(), &config);
+        let sender = intents.sender();
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            power: Some(true),
+            color: Some(GREEN),
+            secondary_color: Some(BLUE),
+            ..Default::default()
+        }));
+        let _ = sender.try_send(LightChangeIntent::GarlandStyle(GarlandStyle {
+            step: Duration::from_secs(3_600),
+            ..GROUPS
+        }));
+
+        let mut frame = [BLACK; 6];
+        for t in 0..20 {
+            frame.copy_from_slice(renderer.render(Instant::from_millis(t * 100)));
+        }
+        assert_eq!(frame, [GREEN, GREEN, BLACK, BLUE, BLUE, BLACK]);
+    }
+
+    #[test]
+    fn test_garland_colors_intent() {
+        let intents = IntentChannel::<8>::new();
+        let config = common::config(EffectId::Garland, 12, RED);
+        let mut renderer = Renderer::<12, 8>::new(intents.receiver(), &config);
+        let sender = intents.sender();
+        let _ = sender.try_send(LightChangeIntent::GarlandStyle(GarlandStyle {
+            group_size: 1,
+            gap: 0,
+            step: Duration::from_secs(3_600),
+            ..GROUPS
+        }));
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            power: Some(true),
+            garland_colors: GarlandColors::new(&[RED, GREEN, BLUE, WHITE]),
+            ..Default::default()
+        }));
+        let mut frame = [BLACK; 12];
+        for t in 0..10 {
+            frame.copy_from_slice(renderer.render(Instant::from_millis(t * 100)));
+        }
+        assert_eq!(frame[..4], [RED, GREEN, BLUE, WHITE]);
+
+        // Slot colors replace single colors of the set
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            secondary_color: Some(BLACK),
+            ..Default::default()
+        }));
+        for t in 10..20 {
+            frame.copy_from_slice(renderer.render(Instant::from_millis(t * 100)));
+        }
+        assert_eq!(frame[..4], [RED, BLACK, BLUE, WHITE]);
+
+        // A shorter set goes back to fewer colors
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            garland_colors: GarlandColors::new(&[GREEN, BLUE]),
+            ..Default::default()
+        }));
+        for t in 20..30 {
+            frame.copy_from_slice(renderer.render(Instant::from_millis(t * 100)));
+        }
+        assert_eq!(frame[..4], [GREEN, BLUE, GREEN, BLUE]);
+    }
+}
@@ -0,0 +1,106 @@
+mod common;
+
+mod tests {
+    use myrtio_light_composer::{
+        Duration,
+        EffectId,
+        Instant,
+        IntentChannel,
+        LightChangeIntent,
+        LightStateIntent,
+        Renderer,
+        color::Rgb,
+        effect::{Effect, GAUGE_FULL, GaugeEffect, GaugeStyle, percent_to_level},
+    };
+
+    use crate::common;
+
+    const GREEN: Rgb = Rgb { r: 0, g: 255, b: 0 };
+    const RED: Rgb = Rgb { r: 255, g: 0, b: 0 };
+    const BLACK: Rgb = Rgb { r: 0, g: 0, b: 0 };
+
+    const STILL: GaugeStyle = GaugeStyle {
+        empty: BLACK,
+        gradient_from: None,
+        animated_edge: false,
+    };
+
+    #[test]
+    fn test_percent_to_level() {
+        assert_eq!(percent_to_level(0), 0);
+        assert_eq!(percent_to_level(50), 32767);
+        assert_eq!(percent_to_level(100), GAUGE_FULL);
+        assert_eq!(percent_to_level(200), GAUGE_FULL);
+    }
+
+    #[test]
+    fn test_gauge_fill() {
+        let mut effect = GaugeEffect::new(GREEN)
+            .with_style(STILL)
+            .with_level(percent_to_level(50));
+        let mut leds = [RED; 4];
+        effect.render(Instant::from_millis(0), &mut leds);
+        assert_eq!(leds[..2], [GREEN, GREEN]);
+        assert_eq!(leds[3], BLACK);
+    }
+
+    #[test]
+    fn test_gauge_partial_led() {
+        let mut effect = GaugeEffect::new(GREEN)
+            .with_style(STILL)
+            .with_level(percent_to_level(25));
+        let mut leds = [BLACK; 2];
+        effect.render(Instant::from_millis(0), &mut leds);
+        assert!(leds[0].g > 100 && leds[0].g < 155, "g = {}", leds[0].g);
+        assert_eq!(leds[1], BLACK);
+    }
+
+    #[test]
+    fn test_gauge_gradient() {
+        let mut effect = GaugeEffect::new(GREEN)
+            .with_style(GaugeStyle {
+                gradient_from: Some(RED),
+                ..STILL
+            })
+            .with_level(GAUGE_FULL);
+        let mut leds = [BLACK; 3];
+        effect.render(Instant::from_millis(0), &mut leds);
+        assert_eq!(leds[0], RED);
+        assert_eq!(leds[2], GREEN);
+    }
+
+    #[test]
+    fn test_gauge_level_animates() {
+        let mut effect = GaugeEffect::new(GREEN)
+            .with_style(STILL)
+            .with_level_transition(Duration::from_millis(100));
+        let mut leds = [BLACK; 4];
+        effect.set_level(GAUGE_FULL, Instant::from_millis(0));
+
+        effect.render(Instant::from_millis(50), &mut leds);
+        assert!(effect.level() > 0 && effect.level() < GAUGE_FULL);
+        effect.render(Instant::from_millis(100), &mut leds);
+        assert_eq!(effect.level(), GAUGE_FULL);
+        assert_eq!(leds, [GREEN; 4]);
+    }
+
+    #[test]
+    fn test_gauge_level_intent() {
+        let intents = IntentChannel::<8>::new();
+        let config = common::config(EffectId::Gauge, 4, GREEN);
+        let mut renderer = Renderer::<4, 8>::new(intents.receiver(), &config);
+        let sender = intents.sender();
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            power: Some(true),
+            ..Default::default()
+        }));
+        let _ = sender.try_send(LightChangeIntent::GaugeStyle(STILL));
+        let _ = sender.try_send(LightChangeIntent::Level(percent_to_level(50)));
+
+        let mut frame = [BLACK; 4];
+        for t in 0..20 {
+            frame.copy_from_slice(renderer.render(Instant::from_millis(t * 100)));
+        }
+        assert_eq!(frame, [GREEN, GREEN, BLACK, BLACK]);
+    }
+}
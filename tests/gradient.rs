@@ -0,0 +1,97 @@
+mod tests {
+    use myrtio_light_composer::{
+        Duration,
+        Instant,
+        color::{ColorStop, Rgb, sample_stops},
+        effect::{Effect, GradientConfig, GradientEffect, MAX_GRADIENT_STOPS},
+    };
+
+    const RED: Rgb = Rgb { r: 255, g: 0, b: 0 };
+    const BLUE: Rgb = Rgb { r: 0, g: 0, b: 255 };
+    const BLACK: Rgb = Rgb { r: 0, g: 0, b: 0 };
+
+    #[test]
+    fn test_sample_stops_clamps_ends() {
+        let stops = [ColorStop::new(64, RED), ColorStop::new(192, BLUE)];
+        assert_eq!(sample_stops(&stops, 0, false), RED);
+        assert_eq!(sample_stops(&stops, 64, false), RED);
+        assert_eq!(sample_stops(&stops, 192, false), BLUE);
+        assert_eq!(sample_stops(&stops, 255, false), BLUE);
+
+        let middle = sample_stops(&stops, 128, false);
+        assert!(middle.r > 100 && middle.r < 155);
+        assert!(middle.b > 100 && middle.b < 155);
+    }
+
+    #[test]
+    fn test_sample_stops_cyclic_wraps() {
+        let stops = [ColorStop::new(64, RED), ColorStop::new(192, BLUE)];
+        // Halfway between the last stop and the first one, across the wrap
+        let wrapped = sample_stops(&stops, 0, true);
+        assert!(wrapped.r > 100 && wrapped.r < 155);
+        assert!(wrapped.b > 100 && wrapped.b < 155);
+        assert_eq!(sample_stops(&stops, 192, true), BLUE);
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(GradientConfig::from_colors(&[RED]).is_none());
+        assert!(
+            GradientConfig::from_colors(&[RED; MAX_GRADIENT_STOPS + 1]).is_none()
+        );
+        assert!(GradientConfig::from_colors(&[RED, BLUE]).is_some());
+
+        let stops = [ColorStop::new(255, BLUE), ColorStop::new(0, RED)];
+        let config = GradientConfig::from_stops(&stops).unwrap();
+        assert_eq!(config.stops()[0], ColorStop::new(0, RED));
+        assert_eq!(config.stops()[1], ColorStop::new(255, BLUE));
+        assert!(GradientConfig::from_stops(&stops[..1]).is_none());
+    }
+
+    #[test]
+    fn test_static_gradient_spans_strip() {
+        let config = GradientConfig::from_colors(&[RED, BLUE]).unwrap();
+        let mut effect = GradientEffect::new(RED).with_config(config);
+        let mut leds = [BLACK; 10];
+        effect.render(Instant::from_millis(0), &mut leds);
+        assert_eq!(leds[0], RED);
+        assert_eq!(leds[9], BLUE);
+
+        let later = leds;
+        effect.render(Instant::from_millis(5_000), &mut leds);
+        assert_eq!(leds, later);
+    }
+
+    #[test]
+    fn test_mirrored_gradient() {
+        let config = GradientConfig::from_colors(&[RED, BLUE])
+            .unwrap()
+            .with_mirror();
+        let mut effect = GradientEffect::new(RED).with_config(config);
+        let mut leds = [BLACK; 10];
+        effect.render(Instant::from_millis(0), &mut leds);
+        assert_eq!(leds[0], RED);
+        assert_eq!(leds[9], RED);
+        assert_eq!(leds[4], BLUE);
+        for i in 0..5 {
+            assert_eq!(leds[i], leds[9 - i]);
+        }
+    }
+
+    #[test]
+    fn test_scrolling_gradient_moves() {
+        let config = GradientConfig::from_colors(&[RED, BLUE])
+            .unwrap()
+            .with_scroll(Duration::from_millis(1_000));
+        let mut effect = GradientEffect::new(RED).with_config(config);
+        let mut start = [BLACK; 10];
+        let mut later = [BLACK; 10];
+        effect.render(Instant::from_millis(0), &mut start);
+        effect.render(Instant::from_millis(250), &mut later);
+        assert_ne!(start, later);
+
+        // A full period brings the gradient back to its start
+        effect.render(Instant::from_millis(1_000), &mut later);
+        assert_eq!(start, later);
+    }
+}
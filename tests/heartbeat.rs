@@ -0,0 +1,66 @@
+mod tests {
+    use myrtio_light_composer::{
+        Instant,
+        color::Rgb,
+        effect::{Effect, HeartbeatEffect, MAX_BPM},
+    };
+
+    const RED: Rgb = Rgb { r: 255, g: 0, b: 0 };
+    const BLACK: Rgb = Rgb { r: 0, g: 0, b: 0 };
+
+    #[test]
+    fn test_heartbeat_double_pulse() {
+        let mut effect = HeartbeatEffect::new(RED).with_bpm(60);
+        let mut leds = [BLACK; 3];
+
+        // "lub" peaks right after the attack
+        effect.render(Instant::from_millis(40), &mut leds);
+        assert_eq!(leds, [RED; 3]);
+
+        // Quiet between the pulses
+        effect.render(Instant::from_millis(270), &mut leds);
+        assert!(leds[0].r < 16, "r = {}", leds[0].r);
+
+        // "dub" is weaker than "lub"
+        effect.render(Instant::from_millis(282 + 40), &mut leds);
+        assert!(leds[0].r > 128 && leds[0].r < 255, "r = {}", leds[0].r);
+
+        // Rest before the next beat, which repeats one period later
+        effect.render(Instant::from_millis(900), &mut leds);
+        assert_eq!(leds, [BLACK; 3]);
+        effect.render(Instant::from_millis(1040), &mut leds);
+        assert_eq!(leds, [RED; 3]);
+    }
+
+    #[test]
+    fn test_heartbeat_bpm_clamped() {
+        let mut effect = HeartbeatEffect::new(RED);
+        effect.set_bpm(1000);
+        assert_eq!(effect.bpm(), MAX_BPM);
+    }
+
+    #[test]
+    fn test_heartbeat_ripple_is_symmetric() {
+        let mut effect = HeartbeatEffect::new(RED).with_ripple();
+        let mut leds = [BLACK; 8];
+        effect.render(Instant::from_millis(70), &mut leds);
+
+        let mut mirrored = leds;
+        mirrored.reverse();
+        assert_eq!(leds, mirrored);
+        // The center is ahead of the edges
+        assert!(leds[3].r != leds[0].r);
+    }
+
+    #[test]
+    fn test_heartbeat_ripple_center_on_odd_strip() {
+        let mut effect = HeartbeatEffect::new(RED).with_ripple();
+        let mut leds = [BLACK; 5];
+        effect.render(Instant::from_millis(40), &mut leds);
+
+        // The middle LED peaks first, its neighbours trail by one step
+        assert_eq!(leds[2], RED);
+        assert!(leds[1].r < leds[2].r);
+        assert_eq!(leds[1], leds[3]);
+    }
+}
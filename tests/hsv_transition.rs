@@ -0,0 +1,129 @@
+mod common;
+
+mod tests {
+    use myrtio_light_composer::{
+        ColorBlending,
+        Duration,
+        EffectId,
+        Instant,
+        IntentChannel,
+        LightChangeIntent,
+        LightStateIntent,
+        Renderer,
+        color::{GradientDirection, Hsv, Rgb, blend_hsv, rgb2hsv},
+        effect::FlowPalette,
+        transition::ValueTransition,
+    };
+
+    use crate::common;
+
+    const RED: Hsv = Hsv {
+        hue: 0,
+        sat: 255,
+        val: 255,
+    };
+    const BLUE: Hsv = Hsv {
+        hue: 171,
+        sat: 255,
+        val: 255,
+    };
+
+    #[test]
+    fn test_hue_directions() {
+        let shortest = blend_hsv(RED, BLUE, 128, GradientDirection::Shortest);
+        assert_eq!(shortest.hue, 214);
+
+        let forward = blend_hsv(RED, BLUE, 128, GradientDirection::Forward);
+        assert_eq!(forward.hue, 86);
+
+        let backward = blend_hsv(BLUE, RED, 128, GradientDirection::Backward);
+        assert_eq!(backward.hue, 85);
+    }
+
+    #[test]
+    fn test_black_keeps_hue() {
+        let black = Hsv {
+            hue: 0,
+            sat: 0,
+            val: 0,
+        };
+        let fading = blend_hsv(black, BLUE, 128, GradientDirection::Shortest);
+        assert_eq!(fading.hue, BLUE.hue);
+        assert_eq!(fading.val, 128);
+    }
+
+    #[test]
+    fn test_hsv_blended_transition() {
+        let blending = ColorBlending::Hsv(GradientDirection::Shortest);
+        let mut transition =
+            ValueTransition::new(Rgb::new(255, 0, 0), blending.blender());
+        let blue = Rgb::new(0, 0, 255);
+        transition.set(blue, Duration::from_millis(1_000), Instant::from_millis(0));
+        transition.tick(Instant::from_millis(500));
+        let hue = rgb2hsv(transition.current()).hue;
+        assert!(hue > BLUE.hue);
+
+        transition.tick(Instant::from_millis(1_000));
+        assert_eq!(transition.current(), blue);
+        assert!(!transition.is_transitioning());
+    }
+
+    #[test]
+    fn test_color_change_sweeps_through_magenta() {
+        let intents = IntentChannel::<8>::new();
+        let mut config = common::config(EffectId::Static, 2, Rgb::new(255, 0, 0));
+        config.timings.color_change = Duration::from_millis(1_000);
+        let mut renderer = Renderer::<2, 8>::new(intents.receiver(), &config);
+        let sender = intents.sender();
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            power: Some(true),
+            ..Default::default()
+        }));
+        renderer.render(Instant::from_millis(0));
+
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            color: Some(Rgb::new(0, 0, 255)),
+            color_blending: Some(ColorBlending::Hsv(GradientDirection::Shortest)),
+            ..Default::default()
+        }));
+        renderer.render(Instant::from_millis(0));
+
+        let halfway = renderer.render(Instant::from_millis(500))[0];
+        assert_eq!(halfway.g, 0);
+        assert!(halfway.r > 128 && halfway.b > 128);
+
+        let done = renderer.render(Instant::from_millis(1_000))[0];
+        assert_eq!(done, Rgb::new(0, 0, 255));
+    }
+
+    #[test]
+    fn test_blending_override_is_scoped_to_its_transition() {
+        let palette = FlowPalette::new(&[Rgb::new(0, 255, 0)]).unwrap();
+        let mut frames = [[Rgb::default(); 4]; 2];
+        for (override_blending, frame) in [true, false].into_iter().zip(&mut frames)
+        {
+            let intents = IntentChannel::<8>::new();
+            let mut config = common::config(EffectId::Neon, 4, Rgb::new(255, 0, 0));
+            config.timings.color_change = Duration::from_millis(1_000);
+            let mut renderer = Renderer::<4, 8>::new(intents.receiver(), &config);
+            let sender = intents.sender();
+            let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+                power: Some(true),
+                color: Some(Rgb::new(0, 0, 255)),
+                color_blending: override_blending
+                    .then_some(ColorBlending::Hsv(GradientDirection::Shortest)),
+                ..Default::default()
+            }));
+            for t in (0..=2_000).step_by(100) {
+                renderer.render(Instant::from_millis(t));
+            }
+
+            // The palette crossfade uses the configured sRGB blending
+            let _ = sender
+                .try_send(LightChangeIntent::FlowPalette(Some(palette.clone())));
+            renderer.render(Instant::from_millis(3_000));
+            frame.copy_from_slice(renderer.render(Instant::from_millis(3_500)));
+        }
+        assert_eq!(frames[0], frames[1]);
+    }
+}
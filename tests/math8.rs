@@ -1,7 +1,7 @@
 mod tests {
     use myrtio_light_composer::{
         Duration,
-        math8::{blend8, progress8, scale8},
+        math8::{blend8, blend16, cos8, progress8, scale8, sin8},
     };
 
     #[test]
@@ -21,6 +21,14 @@ mod tests {
         assert_eq!(blend8(255, 128, 0), 255);
     }
 
+    #[test]
+    fn test_blend16() {
+        assert_eq!(blend16(0, 65535, 0), 0);
+        assert_eq!(blend16(0, 65535, 255), 65535);
+        assert_eq!(blend16(65535, 0, 255), 0);
+        assert_eq!(blend16(1000, 3000, 51), 1400);
+    }
+
     #[test]
     fn test_progress8() {
         assert_eq!(
@@ -36,4 +44,32 @@ mod tests {
             255
         );
     }
+
+    #[test]
+    fn test_sin8() {
+        assert_eq!(sin8(0), 128);
+        assert_eq!(sin8(64), 255);
+        assert_eq!(sin8(128), 128);
+        assert_eq!(sin8(192), 1);
+    }
+
+    #[test]
+    fn test_sin8_accuracy() {
+        for theta in 0..=255u8 {
+            let angle = f64::from(theta) / 256.0 * core::f64::consts::TAU;
+            let expected = 128.0 + 127.5 * angle.sin();
+            let error = (f64::from(sin8(theta)) - expected).abs();
+            assert!(error <= 4.0, "sin8({theta}) = {}", sin8(theta));
+        }
+    }
+
+    #[test]
+    fn test_cos8() {
+        assert_eq!(cos8(0), 255);
+        assert_eq!(cos8(64), 128);
+        assert_eq!(cos8(128), 1);
+        for theta in 0..=255u8 {
+            assert_eq!(cos8(theta), sin8(theta.wrapping_add(64)));
+        }
+    }
 }
@@ -0,0 +1,77 @@
+mod tests {
+    use myrtio_light_composer::{
+        ColorBlending,
+        Duration,
+        Instant,
+        color::{Oklab, Rgb, blend_colors, blend_oklab},
+        effect::{Effect, StaticColorEffect},
+    };
+
+    const RED: Rgb = Rgb { r: 255, g: 0, b: 0 };
+    const GREEN: Rgb = Rgb { r: 0, g: 255, b: 0 };
+    const BLACK: Rgb = Rgb { r: 0, g: 0, b: 0 };
+
+    #[test]
+    fn test_round_trip() {
+        for value in 0..=255 {
+            let gray = Rgb {
+                r: value,
+                g: value,
+                b: value,
+            };
+            assert_eq!(Oklab::from_rgb(gray).to_rgb(), gray);
+        }
+        let orange = Rgb {
+            r: 255,
+            g: 128,
+            b: 10,
+        };
+        assert_eq!(Oklab::from_rgb(orange).to_rgb(), orange);
+
+        let white = Oklab::from_rgb(Rgb {
+            r: 255,
+            g: 255,
+            b: 255,
+        });
+        assert!((white.l - 1.0).abs() < 0.001);
+        assert!(white.a.abs() < 0.001 && white.b.abs() < 0.001);
+    }
+
+    #[test]
+    fn test_blend_endpoints() {
+        assert_eq!(blend_oklab(RED, GREEN, 0), RED);
+        assert_eq!(blend_oklab(RED, GREEN, 255), GREEN);
+        assert_eq!(blend_oklab(BLACK, BLACK, 128), BLACK);
+    }
+
+    #[test]
+    fn test_midpoint_keeps_lightness() {
+        let srgb = blend_colors(RED, GREEN, 128);
+        let oklab = blend_oklab(RED, GREEN, 128);
+        let lightness = |color| Oklab::from_rgb(color).l;
+
+        // Perceived lightness stays between the two ends
+        let low = lightness(RED).min(lightness(GREEN));
+        assert!(lightness(oklab) > low);
+        assert!(lightness(srgb) < low);
+    }
+
+    #[test]
+    fn test_static_effect_blending() {
+        let mut effect = StaticColorEffect::new(RED);
+        effect.set_blending(ColorBlending::Oklab);
+        effect.set_color(
+            GREEN,
+            Duration::from_millis(1_000),
+            Instant::from_millis(0),
+        );
+
+        let mut leds = [BLACK; 2];
+        effect.render(Instant::from_millis(500), &mut leds);
+        let halfway = leds[0];
+        assert!(u16::from(halfway.r) + u16::from(halfway.g) > 255);
+
+        effect.render(Instant::from_millis(1_000), &mut leds);
+        assert_eq!(leds[0], GREEN);
+    }
+}
@@ -0,0 +1,130 @@
+mod common;
+
+mod tests {
+    use myrtio_light_composer::{
+        Duration,
+        EffectId,
+        Instant,
+        IntentChannel,
+        LightChangeIntent,
+        LightEngineConfig,
+        LightStateIntent,
+        Overlay,
+        OverlayLength,
+        OverlayPattern,
+        Renderer,
+        color::Rgb,
+    };
+
+    use crate::common;
+
+    const RED: Rgb = Rgb { r: 255, g: 0, b: 0 };
+    const GREEN: Rgb = Rgb { r: 0, g: 255, b: 0 };
+    const BLUE: Rgb = Rgb { r: 0, g: 0, b: 255 };
+    const BLACK: Rgb = Rgb { r: 0, g: 0, b: 0 };
+
+    fn config() -> LightEngineConfig {
+        common::config(EffectId::Static, 2, GREEN)
+    }
+
+    #[test]
+    fn test_overlay_duration() {
+        assert_eq!(
+            Overlay::blink(RED, 3).duration(),
+            Duration::from_millis(1_500)
+        );
+        let overlay = Overlay::new(
+            OverlayPattern::Solid,
+            RED,
+            OverlayLength::Duration(Duration::from_secs(5)),
+        );
+        assert!(!overlay.is_finished(Duration::from_millis(4_999)));
+        assert!(overlay.is_finished(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_blink_and_pulse_levels() {
+        let mut leds = [BLACK; 2];
+        let blink = Overlay::blink(RED, 3);
+        blink.render(Duration::from_millis(100), &mut leds);
+        assert_eq!(leds, [RED, RED]);
+        blink.render(Duration::from_millis(300), &mut leds);
+        assert_eq!(leds, [BLACK, BLACK]);
+
+        let pulse = Overlay::pulse(BLUE, Duration::from_secs(5));
+        pulse.render(Duration::from_millis(0), &mut leds);
+        assert!(leds[0].b < 8);
+        pulse.render(Duration::from_millis(500), &mut leds);
+        assert!(leds[0].b > 240);
+
+        let dim = Overlay::blink(RED, 1).with_brightness(128);
+        dim.render(Duration::from_millis(0), &mut leds);
+        assert_eq!(leds[0].r, 128);
+    }
+
+    #[test]
+    fn test_overlay_restores_running_effect() {
+        let intents = IntentChannel::<8>::new();
+        let mut renderer = Renderer::<2, 8>::new(intents.receiver(), &config());
+        let sender = intents.sender();
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            power: Some(true),
+            ..Default::default()
+        }));
+        assert_eq!(renderer.render(Instant::from_millis(0)), [GREEN, GREEN]);
+
+        let _ = sender
+            .try_send(LightChangeIntent::Overlay(Some(Overlay::blink(RED, 2))));
+        assert_eq!(renderer.render(Instant::from_millis(100)), [RED, RED]);
+        assert_eq!(renderer.render(Instant::from_millis(400)), [BLACK, BLACK]);
+        assert!(renderer.is_overlay_active());
+
+        assert_eq!(renderer.render(Instant::from_millis(1_100)), [GREEN, GREEN]);
+        assert!(!renderer.is_overlay_active());
+    }
+
+    #[test]
+    fn test_overlay_while_off() {
+        let intents = IntentChannel::<8>::new();
+        let mut renderer = Renderer::<2, 8>::new(intents.receiver(), &config());
+        let sender = intents.sender();
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            power: Some(false),
+            ..Default::default()
+        }));
+        for t in 0..5 {
+            renderer.render(Instant::from_millis(t * 10));
+        }
+        assert_eq!(renderer.render(Instant::from_millis(50)), [BLACK, BLACK]);
+
+        let overlay = Overlay::new(
+            OverlayPattern::Solid,
+            BLUE,
+            OverlayLength::Duration(Duration::from_millis(500)),
+        );
+        let _ = sender.try_send(LightChangeIntent::Overlay(Some(overlay)));
+        assert_eq!(renderer.render(Instant::from_millis(100)), [BLUE, BLUE]);
+        assert_eq!(renderer.render(Instant::from_millis(700)), [BLACK, BLACK]);
+    }
+
+    #[test]
+    fn test_cancel_overlay() {
+        let intents = IntentChannel::<8>::new();
+        let mut renderer = Renderer::<2, 8>::new(intents.receiver(), &config());
+        let sender = intents.sender();
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            power: Some(true),
+            ..Default::default()
+        }));
+        let _ = sender.try_send(LightChangeIntent::Overlay(Some(Overlay::pulse(
+            RED,
+            Duration::from_secs(60),
+        ))));
+        renderer.render(Instant::from_millis(0));
+        assert!(renderer.is_overlay_active());
+
+        let _ = sender.try_send(LightChangeIntent::Overlay(None));
+        assert_eq!(renderer.render(Instant::from_millis(100)), [GREEN, GREEN]);
+        assert!(!renderer.is_overlay_active());
+    }
+}
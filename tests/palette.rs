@@ -0,0 +1,88 @@
+mod tests {
+    use myrtio_light_composer::color::{
+        ColorStop,
+        MAX_PALETTE_STOPS,
+        PALETTE_ENTRIES,
+        Palette,
+        Rgb,
+    };
+
+    const RED: Rgb = Rgb { r: 255, g: 0, b: 0 };
+    const BLUE: Rgb = Rgb { r: 0, g: 0, b: 255 };
+    const BLACK: Rgb = Rgb { r: 0, g: 0, b: 0 };
+    const WHITE: Rgb = Rgb {
+        r: 255,
+        g: 255,
+        b: 255,
+    };
+
+    #[test]
+    fn test_entries_blend_and_wrap() {
+        let heat = &Palette::HEAT;
+        assert_eq!(heat.len(), PALETTE_ENTRIES);
+        assert_eq!(heat.color_from_palette(0, 255, true), BLACK);
+        assert_eq!(heat.color_from_palette(0xF0, 255, true), WHITE);
+
+        // Halfway between two entries
+        let between = heat.color_from_palette(0x08, 255, true);
+        assert_eq!(between, Rgb { r: 26, g: 0, b: 0 });
+        assert_eq!(heat.color_from_palette(0x08, 255, false), BLACK);
+
+        // The last entry blends back into the first
+        let wrapped = heat.color_from_palette(0xF8, 255, true);
+        assert!(wrapped.r > 100 && wrapped.r < 150);
+    }
+
+    #[test]
+    fn test_brightness_scales_color() {
+        let color = Palette::RAINBOW.color_from_palette(0, 128, true);
+        assert_eq!(color, Rgb { r: 128, g: 0, b: 0 });
+        assert_eq!(Palette::RAINBOW.color_from_palette(0, 0, true), BLACK);
+    }
+
+    #[test]
+    fn test_stops_sorted_and_sampled() {
+        let palette = Palette::from_stops(&[
+            ColorStop::new(255, BLUE),
+            ColorStop::new(0, RED),
+        ])
+        .unwrap();
+        assert_eq!(palette.sample(0), RED);
+        assert_eq!(palette.sample(255), BLUE);
+        assert_eq!(palette.color_from_palette(200, 255, false), RED);
+
+        let middle = palette.sample(128);
+        assert!(middle.r > 100 && middle.b > 100);
+    }
+
+    #[test]
+    fn test_palette_validation() {
+        assert!(Palette::from_stops(&[]).is_none());
+        assert!(Palette::from_colors(&[]).is_none());
+        assert!(Palette::from_colors(&[RED; MAX_PALETTE_STOPS + 1]).is_none());
+        assert_eq!(Palette::from_colors(&[RED]).unwrap().sample(200), RED);
+    }
+
+    #[test]
+    fn test_colors_spread_evenly() {
+        let palette = Palette::from_colors(&[RED, WHITE, BLUE]).unwrap();
+        assert_eq!(palette.sample(0), RED);
+        assert_eq!(palette.sample(127), WHITE);
+        assert_eq!(palette.sample(255), BLUE);
+    }
+
+    #[test]
+    fn test_static_stops_match_colors() {
+        const STOPS: [ColorStop; 3] = Palette::spread_stops([RED, WHITE, BLUE]);
+        let palette = Palette::StaticStops(&STOPS);
+        let colors = Palette::from_colors(&[RED, WHITE, BLUE]).unwrap();
+        assert_eq!(palette.len(), 3);
+        for index in 0..=255 {
+            assert_eq!(palette.sample(index), colors.sample(index));
+            assert_eq!(
+                palette.color_from_palette(index, 255, false),
+                colors.color_from_palette(index, 255, false)
+            );
+        }
+    }
+}
@@ -0,0 +1,32 @@
+mod tests {
+    use myrtio_light_composer::{
+        Instant,
+        color::Rgb,
+        effect::{Effect, PlasmaEffect},
+    };
+
+    fn frame(effect: &mut PlasmaEffect, now_ms: u64) -> [Rgb; 32] {
+        let mut leds = [Rgb::default(); 32];
+        effect.render(Instant::from_millis(now_ms), &mut leds);
+        leds
+    }
+
+    #[test]
+    fn test_plasma_deterministic() {
+        let mut effect = PlasmaEffect::new();
+        let first = frame(&mut effect, 1_234);
+        assert_eq!(frame(&mut effect, 1_234), first);
+        assert_eq!(frame(&mut PlasmaEffect::new(), 1_234), first);
+    }
+
+    #[test]
+    fn test_plasma_moves_over_time() {
+        let mut effect = PlasmaEffect::new();
+        let start = frame(&mut effect, 0);
+        assert_ne!(frame(&mut effect, 500), start);
+
+        // Neighboring LEDs are on different parts of the waves
+        assert!(start.iter().any(|led| *led != start[0]));
+        assert!(start.iter().all(|led| *led != Rgb::default()));
+    }
+}
@@ -0,0 +1,105 @@
+mod tests {
+    use myrtio_light_composer::{
+        Instant,
+        color::Rgb,
+        effect::{Effect, MAX_RAIN_DROPS, RainEffect, RainStyle},
+    };
+
+    const BLUE: Rgb = Rgb { r: 0, g: 0, b: 255 };
+    const BLACK: Rgb = Rgb { r: 0, g: 0, b: 0 };
+
+    #[test]
+    fn test_rain_drop_lifecycle() {
+        let mut effect = RainEffect::new(BLUE).with_style(RainStyle {
+            density: 2,
+            speed: 128,
+        });
+        let mut leds = [BLACK; 30];
+        let mut formed = false;
+        let mut fell = false;
+        let mut splashed = false;
+
+        for t in (0..15_000).step_by(10) {
+            effect.render(Instant::from_millis(t), &mut leds);
+            let lit: Vec<usize> =
+                (0..leds.len()).filter(|&i| leds[i] != BLACK).collect();
+            if lit == [0] {
+                formed = true;
+            }
+            if lit.iter().any(|&i| i > 5 && i < 25) {
+                fell = true;
+            }
+            // Splash flash mixes white into the drop color
+            if leds[29].r > 0 {
+                splashed = true;
+            }
+        }
+
+        assert!(formed && fell && splashed);
+    }
+
+    #[test]
+    fn test_rain_is_bounded() {
+        let mut effect = RainEffect::new(BLUE).with_style(RainStyle {
+            density: 255,
+            speed: 1,
+        });
+        let mut leds = [BLACK; 200];
+        for t in (0..20_000).step_by(5) {
+            effect.render(Instant::from_millis(t), &mut leds);
+            assert!(effect.drop_count() <= MAX_RAIN_DROPS);
+        }
+        assert_eq!(effect.drop_count(), MAX_RAIN_DROPS);
+    }
+
+    #[test]
+    fn test_rain_no_density() {
+        let mut effect = RainEffect::new(BLUE).with_style(RainStyle {
+            density: 0,
+            speed: 128,
+        });
+        let mut leds = [BLUE; 10];
+        for t in (0..5_000).step_by(50) {
+            effect.render(Instant::from_millis(t), &mut leds);
+        }
+        assert_eq!(leds, [BLACK; 10]);
+    }
+
+    #[test]
+    fn test_zero_speed_still_falls() {
+        let mut effect = RainEffect::new(BLUE);
+        effect.set_style(RainStyle {
+            density: 255,
+            speed: 0,
+        });
+        let mut leds = [BLACK; 4];
+        let mut splashes = 0;
+        for t in (0..30_000).step_by(10) {
+            effect.render(Instant::from_millis(t), &mut leds);
+            if leds[3].r > 0 {
+                splashes += 1;
+            }
+        }
+        // Drops reach the end and make room for new ones
+        assert!(splashes > 0);
+    }
+
+    #[test]
+    fn test_long_pause_splashes_drops() {
+        let mut effect = RainEffect::new(BLUE).with_style(RainStyle {
+            density: 255,
+            speed: 1,
+        });
+        let mut leds = [BLACK; 200];
+        for t in (0..20_000).step_by(5) {
+            effect.render(Instant::from_millis(t), &mut leds);
+        }
+
+        // Not rendered for 30 days, e.g. while an overlay was shown
+        let resumed = 20_000 + 30 * 24 * 60 * 60 * 1000;
+        effect.render(Instant::from_millis(resumed), &mut leds);
+        assert!(leds[199].r > 0);
+        effect.render(Instant::from_millis(resumed + 1_000), &mut leds);
+        assert!(effect.drop_count() <= 1);
+    }
+}
@@ -0,0 +1,117 @@
+mod tests {
+    use myrtio_light_composer::{
+        ColorTemperature,
+        OutputDriver,
+        RgbwOutput,
+        RgbwOutputDriver,
+        color::{Rgb, Rgbw, WhiteExtraction, kelvin_to_rgb, rgbw},
+    };
+
+    #[derive(Default)]
+    struct Recorder {
+        frame: Vec<Rgbw>,
+    }
+
+    impl RgbwOutputDriver for Recorder {
+        fn write_rgbw(&mut self, colors: &[Rgbw]) {
+            self.frame = colors.to_vec();
+        }
+    }
+
+    const PINK: Rgb = Rgb {
+        r: 255,
+        g: 100,
+        b: 150,
+    };
+
+    #[test]
+    fn test_off_keeps_rgb() {
+        assert_eq!(WhiteExtraction::Off.to_rgbw(PINK), rgbw(PINK, 0));
+    }
+
+    #[test]
+    fn test_min_subtract() {
+        let pixel = WhiteExtraction::MinSubtract.to_rgbw(PINK);
+        assert_eq!(pixel, rgbw(Rgb::new(155, 0, 50), 100));
+
+        let white = WhiteExtraction::MinSubtract.to_rgbw(Rgb::new(200, 200, 200));
+        assert_eq!(white, rgbw(Rgb::new(0, 0, 0), 200));
+    }
+
+    #[test]
+    fn test_white_led_match() {
+        let extraction = WhiteExtraction::white_led(4_000);
+
+        // The white LED's own color goes to W alone
+        let pixel = extraction.to_rgbw(kelvin_to_rgb(4_000));
+        assert_eq!(pixel, rgbw(Rgb::new(0, 0, 0), 255));
+
+        // Warmer temperatures keep the extra red on RGB
+        let warm = extraction.to_rgbw(kelvin_to_rgb(2_700));
+        assert!(warm.a.0 > 100);
+        assert!(warm.r > warm.g && warm.b <= 1);
+
+        // Saturated colors have no white part
+        let red = extraction.to_rgbw(Rgb::new(255, 0, 0));
+        assert_eq!(red, rgbw(Rgb::new(255, 0, 0), 0));
+    }
+
+    #[test]
+    fn test_rgbw_output_converts_frames() {
+        let mut output = RgbwOutput::<Recorder, 4>::new(
+            Recorder::default(),
+            WhiteExtraction::MinSubtract,
+        );
+        output.write(&[PINK, Rgb::new(10, 10, 10)]);
+        assert_eq!(
+            output.driver().frame,
+            [rgbw(Rgb::new(155, 0, 50), 100), rgbw(Rgb::new(0, 0, 0), 10)]
+        );
+
+        output.set_extraction(WhiteExtraction::Off);
+        output.write(&[PINK]);
+        assert_eq!(output.driver().frame, [rgbw(PINK, 0)]);
+    }
+
+    #[test]
+    fn test_temperature_drives_white() {
+        let warm = kelvin_to_rgb(2_700);
+        assert_eq!(
+            WhiteExtraction::MinSubtract.temperature_to_rgbw(warm),
+            rgbw(Rgb::new(0, 0, 0), 255)
+        );
+        assert_eq!(
+            WhiteExtraction::Off.temperature_to_rgbw(warm),
+            rgbw(warm, 0)
+        );
+
+        // Only the tint the white LED lacks is mixed from RGB
+        let extraction = WhiteExtraction::white_led(4_000);
+        let pixel = extraction.temperature_to_rgbw(kelvin_to_rgb(4_000));
+        assert_eq!(pixel, rgbw(Rgb::new(0, 0, 0), 255));
+        let pixel = extraction.temperature_to_rgbw(Rgb::new(128, 100, 50));
+        assert_eq!(pixel.a.0, 128);
+    }
+
+    #[test]
+    fn test_rgbw_output_follows_color_temperature() {
+        let mut output = RgbwOutput::<Recorder, 4>::new(
+            Recorder::default(),
+            WhiteExtraction::MinSubtract,
+        );
+        let warm = kelvin_to_rgb(2_700);
+        output.set_color_temperature(Some(ColorTemperature {
+            kelvin: 2_700,
+            amount: 255,
+        }));
+        output.write(&[warm]);
+        assert_eq!(output.driver().frame, [rgbw(Rgb::new(0, 0, 0), 255)]);
+
+        output.set_color_temperature(None);
+        output.write(&[warm]);
+        assert_eq!(
+            output.driver().frame,
+            [WhiteExtraction::MinSubtract.to_rgbw(warm)]
+        );
+    }
+}
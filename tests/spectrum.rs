@@ -0,0 +1,140 @@
+mod common;
+
+mod tests {
+    use myrtio_light_composer::{
+        AudioFeed,
+        AudioInput,
+        EffectId,
+        Instant,
+        IntentChannel,
+        LightChangeIntent,
+        LightStateIntent,
+        Renderer,
+        Spectrum,
+        audio::{AUDIO_STALE_TIMEOUT, MAX_SPECTRUM_BINS, MIN_SPECTRUM_BINS},
+        color::Rgb,
+        effect::{Effect, SpectrumEffect},
+    };
+
+    use crate::common;
+
+    const BLACK: Rgb = Rgb { r: 0, g: 0, b: 0 };
+    const FRAME_MS: u64 = 20;
+
+    /// Feed and render `frames` spectra starting at `from_ms`
+    ///
+    /// Returns the time of the next frame.
+    fn feed(
+        effect: &mut SpectrumEffect,
+        bins: &[u16],
+        from_ms: u64,
+        frames: u64,
+    ) -> u64 {
+        let spectrum = Spectrum::new(bins).unwrap();
+        let mut leds = [BLACK; 16];
+        for frame in 0..frames {
+            effect.set_spectrum(&spectrum);
+            effect
+                .render(Instant::from_millis(from_ms + frame * FRAME_MS), &mut leds);
+        }
+        from_ms + frames * FRAME_MS
+    }
+
+    #[test]
+    fn test_spectrum_validation() {
+        assert!(Spectrum::new(&[0; MIN_SPECTRUM_BINS - 1]).is_none());
+        assert!(Spectrum::new(&[0; MAX_SPECTRUM_BINS + 1]).is_none());
+        assert_eq!(
+            Spectrum::new(&[1; MIN_SPECTRUM_BINS]).unwrap().bins().len(),
+            MIN_SPECTRUM_BINS
+        );
+    }
+
+    #[test]
+    fn test_levels_follow_loudest_bin() {
+        let mut effect = SpectrumEffect::new();
+        let mut bins = [0; 8];
+        bins[2] = 4_000;
+        bins[5] = 2_000;
+        feed(&mut effect, &bins, 0, 10);
+
+        let levels = effect.levels();
+        assert!(levels[2] > 250);
+        assert!(levels[5] > 120 && levels[5] < 135);
+        assert_eq!(levels[0], 0);
+    }
+
+    #[test]
+    fn test_auto_gain_recovers_after_loud_input() {
+        let mut effect = SpectrumEffect::new();
+        let t = feed(&mut effect, &[10_000; 8], 0, 10);
+        let t = feed(&mut effect, &[1_000; 8], t, 5);
+        let quiet = effect.levels()[0];
+        feed(&mut effect, &[1_000; 8], t, 500);
+        assert!(effect.levels()[0] > quiet);
+        assert!(effect.levels()[0] > 250);
+    }
+
+    #[test]
+    fn test_segments_and_peak_falloff() {
+        let mut effect = SpectrumEffect::new();
+        let mut leds = [BLACK; 16];
+        let t = feed(&mut effect, &[5_000; 8], 0, 10);
+        effect.render(Instant::from_millis(t), &mut leds);
+
+        // Two LEDs per bin, hue changes between segments
+        assert_eq!(leds[0], leds[1]);
+        assert_ne!(leds[1], leds[2]);
+
+        // Levels drop, the peak glow fades out over time
+        let t = feed(&mut effect, &[0; 8], t, 40);
+        effect.render(Instant::from_millis(t), &mut leds);
+        assert_eq!(effect.levels(), [0; 8]);
+        assert!(leds.iter().all(|led| *led != BLACK));
+        effect.render(Instant::from_millis(t + 3_000), &mut leds);
+        assert_eq!(leds, [BLACK; 16]);
+    }
+
+    #[test]
+    fn test_stale_spectrum_goes_silent() {
+        let feed = AudioFeed::new();
+        let mut input = AudioInput::new(&feed);
+        assert!(input.spectrum(Instant::from_millis(0)).is_none());
+
+        let spectrum = Spectrum::new(&[100; 8]).unwrap();
+        feed.update_spectrum(&spectrum);
+        assert_eq!(input.spectrum(Instant::from_millis(10)), Some(spectrum));
+        assert!(input.spectrum(Instant::from_millis(20)).is_none());
+
+        let silent = input.spectrum(Instant::from_millis(500)).unwrap();
+        assert_eq!(silent.bins(), [0; 8]);
+        assert!(input.spectrum(Instant::from_millis(600)).is_none());
+    }
+
+    #[test]
+    fn test_stale_feed_goes_dark() {
+        let feed = AudioFeed::new();
+        let intents = IntentChannel::<8>::new();
+        let config = common::config(EffectId::Spectrum, 16, BLACK);
+        let mut renderer = Renderer::<16, 8>::new(intents.receiver(), &config)
+            .with_audio_feed(&feed);
+        let sender = intents.sender();
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            power: Some(true),
+            ..Default::default()
+        }));
+        renderer.render(Instant::from_millis(0));
+
+        feed.update_spectrum(&Spectrum::new(&[5_000; 8]).unwrap());
+        let frame = renderer.render(Instant::from_millis(FRAME_MS));
+        assert!(frame.iter().all(|led| *led != BLACK));
+
+        // No further spectra: the display empties once the feed went stale
+        let end = AUDIO_STALE_TIMEOUT.as_millis() + 3_000;
+        let mut frame = [BLACK; 16];
+        for t in (2 * FRAME_MS..=end).step_by(20) {
+            frame.copy_from_slice(renderer.render(Instant::from_millis(t)));
+        }
+        assert_eq!(frame, [BLACK; 16]);
+    }
+}
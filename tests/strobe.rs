@@ -0,0 +1,128 @@
+mod common;
+
+mod tests {
+    use myrtio_light_composer::{
+        Duration,
+        EffectId,
+        Instant,
+        IntentChannel,
+        LightChangeIntent,
+        LightStateIntent,
+        Renderer,
+        color::Rgb,
+        effect::{
+            Effect,
+            MIN_SAFE_FLASH_INTERVAL,
+            StrobeEffect,
+            StrobePattern,
+            StrobeStyle,
+        },
+    };
+
+    use crate::common;
+
+    const RED: Rgb = Rgb { r: 255, g: 0, b: 0 };
+    const BLUE: Rgb = Rgb { r: 0, g: 0, b: 255 };
+    const BLACK: Rgb = Rgb { r: 0, g: 0, b: 0 };
+
+    #[test]
+    fn test_strobe_rate_is_limited() {
+        let effect = StrobeEffect::new(StrobePattern::Halves)
+            .with_flash_interval(Duration::from_millis(50));
+        assert_eq!(effect.flash_interval(), MIN_SAFE_FLASH_INTERVAL);
+        // Below 3 flashes per second
+        assert!(1000 / effect.flash_interval().as_millis() < 3);
+    }
+
+    #[test]
+    fn test_strobe_unsafe_rate() {
+        let effect = StrobeEffect::new(StrobePattern::Halves)
+            .with_flash_interval(Duration::from_millis(50))
+            .with_unsafe_flash_rate();
+        assert_eq!(effect.flash_interval(), Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_strobe_fastest_unsafe_rate_flashes() {
+        let mut effect = StrobeEffect::new(StrobePattern::Halves)
+            .with_flash_interval(Duration::from_millis(0))
+            .with_unsafe_flash_rate();
+        assert_eq!(effect.flash_interval(), Duration::from_millis(2));
+
+        let mut leds = [BLACK; 2];
+        effect.render(Instant::from_millis(0), &mut leds);
+        assert_eq!(leds, [RED, BLACK]);
+        effect.render(Instant::from_millis(1), &mut leds);
+        assert_eq!(leds, [BLACK; 2]);
+        effect.render(Instant::from_millis(2), &mut leds);
+        assert_eq!(leds, [BLACK, BLUE]);
+    }
+
+    #[test]
+    fn test_strobe_halves() {
+        let mut effect = StrobeEffect::new(StrobePattern::Halves)
+            .with_flash_interval(Duration::from_millis(500));
+        let mut leds = [BLACK; 4];
+
+        effect.render(Instant::from_millis(0), &mut leds);
+        assert_eq!(leds, [RED, RED, BLACK, BLACK]);
+        effect.render(Instant::from_millis(300), &mut leds);
+        assert_eq!(leds, [BLACK; 4]);
+        effect.render(Instant::from_millis(500), &mut leds);
+        assert_eq!(leds, [BLACK, BLACK, BLUE, BLUE]);
+    }
+
+    #[test]
+    fn test_strobe_alternate() {
+        let mut effect = StrobeEffect::new(StrobePattern::Alternate);
+        let mut leds = [BLACK; 4];
+
+        effect.render(Instant::from_millis(0), &mut leds);
+        assert_eq!(leds, [RED, BLACK, RED, BLACK]);
+        effect.render(Instant::from_millis(500), &mut leds);
+        assert_eq!(leds, [BLACK, BLUE, BLACK, BLUE]);
+    }
+
+    #[test]
+    fn test_strobe_quad_flash() {
+        let mut effect = StrobeEffect::new(StrobePattern::QuadFlash);
+        let mut leds = [BLACK; 4];
+
+        for flash in 0..4 {
+            effect.render(Instant::from_millis(flash * 500), &mut leds);
+            assert_eq!(leds, [RED, RED, BLACK, BLACK]);
+        }
+        effect.render(Instant::from_millis(4 * 500), &mut leds);
+        assert_eq!(leds, [BLACK, BLACK, BLUE, BLUE]);
+    }
+
+    #[test]
+    fn test_strobe_style_intent() {
+        let intents = IntentChannel::<8>::new();
+        let config = common::config(EffectId::StrobeAlternate, 4, RED);
+        let mut renderer = Renderer::<4, 8>::new(intents.receiver(), &config);
+        let sender = intents.sender();
+        let _ = sender.try_send(LightChangeIntent::State(LightStateIntent {
+            power: Some(true),
+            ..Default::default()
+        }));
+        let _ = sender.try_send(LightChangeIntent::StrobeStyle(StrobeStyle {
+            interval: Duration::from_millis(100),
+            unsafe_rate: true,
+        }));
+        for t in 0..4 {
+            renderer.render(Instant::from_millis(t));
+        }
+
+        assert_eq!(
+            renderer.render(Instant::from_millis(1000)),
+            [RED, BLACK, RED, BLACK]
+        );
+        // Dark halfway through the interval, lit again in the next one
+        assert_eq!(renderer.render(Instant::from_millis(1050)), [BLACK; 4]);
+        assert_eq!(
+            renderer.render(Instant::from_millis(1100)),
+            [BLACK, BLUE, BLACK, BLUE]
+        );
+    }
+}